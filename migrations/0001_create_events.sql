@@ -0,0 +1,24 @@
+create table if not exists "events" (
+    "id" uuid primary key,
+    "entity_id" uuid not null,
+    "frame_id" bigint not null,
+    "r_um" bigint not null,
+    "lat_code" bigint not null,
+    "lon_code" bigint not null,
+    "version" integer,
+    "causation_id" uuid,
+    "correlation_id" uuid,
+    "ticks" bigint not null,
+    "timestamp" timestamptz not null default now(),
+    "source" text,
+    "author" text,
+    "kind" text not null,
+    "kind_data" jsonb,
+    "move_offset" jsonb,
+    "payload" jsonb
+);
+
+-- per-entity history, in fetch order
+create index if not exists "events_entity_id_ticks_idx" on "events" ("entity_id", "ticks", "id");
+-- time-range queries across entities
+create index if not exists "events_ticks_idx" on "events" ("ticks");
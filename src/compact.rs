@@ -0,0 +1,127 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use crate::playback::Playback;
+use crate::{ChronoEvent, EventKind, PlaybackConfig, SpawnState, TimeDelta, Timeline, UvoxId};
+
+impl Timeline {
+    /// One timeline per entity, each in the original order.
+    pub fn split_by_entity(&self) -> HashMap<UvoxId, Timeline> {
+        let mut parts: HashMap<UvoxId, Timeline> = HashMap::new();
+        for e in &self.events {
+            parts.entry(e.id).or_insert_with(|| self.empty_like()).push(e.clone());
+        }
+        parts
+    }
+
+    /// Split into consecutive windows of `width_ns`, keyed by window start
+    /// (a multiple of `width_ns`). Empty windows are omitted.
+    ///
+    /// # Panics
+    /// If `width_ns` is not positive.
+    pub fn split_windows(&self, width_ns: i64) -> BTreeMap<i64, Timeline> {
+        assert!(width_ns > 0, "window width must be positive");
+        let mut windows: BTreeMap<i64, Timeline> = BTreeMap::new();
+        for e in &self.events {
+            let start = e.t.ticks("nanoseconds").div_euclid(width_ns) * width_ns;
+            windows.entry(start).or_insert_with(|| self.empty_like()).push(e.clone());
+        }
+        windows
+    }
+
+    /// Split into events at or before `cutoff_ns` (what `playback_until`
+    /// applies) and events after it.
+    pub fn split_at(&self, cutoff_ns: i64) -> (Timeline, Timeline) {
+        let (before, after) = self
+            .events
+            .iter()
+            .cloned()
+            .partition(|e| e.t.ticks("nanoseconds") <= cutoff_ns);
+        (
+            Timeline { events: before, schemas: self.schemas.clone() },
+            Timeline { events: after, schemas: self.schemas.clone() },
+        )
+    }
+
+    pub fn compact(&self, cutoff_ns: i64) -> Timeline {
+        self.compact_with(cutoff_ns, &PlaybackConfig::default())
+    }
+
+    /// Replace every event at or before `cutoff_ns` with a `Spawn` snapshot
+    /// per entity (followed by a `Despawn` for dead ones) carrying its
+    /// reconstructed state, so playback of the result matches playback of
+    /// the original. `Ambient` events still in effect at the cutoff are kept,
+    /// moved to the cutoff.
+    ///
+    /// Amendments are resolved into the snapshots; those after the cutoff
+    /// that target compacted events are dropped. A `Move` straddling the
+    /// cutoff is not interpolated as `playback_until` would.
+    pub fn compact_with(&self, cutoff_ns: i64, config: &PlaybackConfig) -> Timeline {
+        let (head, tail) = self.split_at(cutoff_ns);
+        if head.is_empty() {
+            return tail;
+        }
+
+        let mut playback = Playback::new(config.clone());
+        for e in self.effective_events(config) {
+            if e.t.ticks("nanoseconds") > cutoff_ns {
+                break;
+            }
+            playback.apply(&e);
+        }
+        playback.advance_to(cutoff_ns);
+
+        let t = TimeDelta::from_ticks(cutoff_ns, "nanoseconds");
+        let recorded_at = head.events.iter().map(|e| e.recorded_at).max().unwrap_or_default();
+        let compacted: HashSet<_> = head.events.iter().map(|e| e.event_id).collect();
+
+        // Fields still active keep imposing themselves after the cutoff.
+        // Re-issued after the snapshots so they don't touch entities that
+        // were already dead when the field began.
+        let fields: Vec<ChronoEvent> = head
+            .events
+            .into_iter()
+            .filter(|e| match &e.kind {
+                EventKind::Ambient { until, .. } => {
+                    until.as_ref().is_none_or(|u| u.ticks("nanoseconds") > cutoff_ns)
+                }
+                _ => false,
+            })
+            .map(|e| ChronoEvent { t: t.clone(), ..e })
+            .collect();
+
+        let mut events = Vec::new();
+
+        let mut states: Vec<_> = playback.into_state().into_iter().collect();
+        states.sort_by_key(|(id, _)| id.as_tuple());
+        for (id, s) in states {
+            let snapshot = SpawnState {
+                pos: Some(s.pos),
+                temperature: Some(s.temperature),
+                pressure: Some(s.pressure),
+                thermal_tau_s: s.thermal_tau_s,
+                material: s.material,
+                thresholds: Some(s.thresholds),
+                fields: s.fields,
+            };
+            events.push(
+                ChronoEvent::new(id, t.clone(), EventKind::Spawn)
+                    .with_payload(snapshot.to_payload())
+                    .with_recorded_at(recorded_at),
+            );
+            if !s.alive {
+                events.push(ChronoEvent::new(id, t.clone(), EventKind::Despawn).with_recorded_at(recorded_at));
+            }
+        }
+
+        events.extend(fields);
+        events.extend(
+            tail.events
+                .into_iter()
+                .filter(|e| e.amends().is_none_or(|target| !compacted.contains(&target))),
+        );
+        Timeline { events, schemas: self.schemas.clone() }
+    }
+
+    fn empty_like(&self) -> Timeline {
+        Timeline { events: Vec::new(), schemas: self.schemas.clone() }
+    }
+}
@@ -0,0 +1,41 @@
+use serde::{Serialize, Deserialize};
+
+/// Derived operating condition of an entity, from its temperature and pressure.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Condition {
+    #[default]
+    Nominal,
+    /// At or below the freezing threshold.
+    Frozen,
+    /// At or above the overheat threshold.
+    Overheated,
+    /// At or above the maximum pressure.
+    OverPressure,
+}
+
+/// Limits an entity's condition is judged against. Unset limits never trip.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Thresholds {
+    /// Freezing point (°C).
+    pub freeze_c: Option<f64>,
+    /// Overheat point (°C).
+    pub overheat_c: Option<f64>,
+    /// Maximum safe pressure (Pascals).
+    pub max_pressure_pa: Option<f64>,
+}
+
+impl Thresholds {
+    /// Classify a temperature/pressure pair. Over-pressure takes precedence,
+    /// then overheating, then freezing.
+    pub fn evaluate(&self, temperature: f64, pressure: f64) -> Condition {
+        if self.max_pressure_pa.is_some_and(|max| pressure >= max) {
+            Condition::OverPressure
+        } else if self.overheat_c.is_some_and(|max| temperature >= max) {
+            Condition::Overheated
+        } else if self.freeze_c.is_some_and(|min| temperature <= min) {
+            Condition::Frozen
+        } else {
+            Condition::Nominal
+        }
+    }
+}
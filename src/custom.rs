@@ -0,0 +1,24 @@
+use serde::{Serialize, de::DeserializeOwned};
+use crate::{ChronoEvent, EntityState};
+
+/// A strongly-typed event kind defined outside this crate.
+///
+/// Carried as `EventKind::Custom(NAME)` with the serialized data in the
+/// payload, so it round-trips through serde and persistence unchanged.
+pub trait CustomEvent: Serialize + DeserializeOwned {
+    /// Tag stored in `EventKind::Custom`; must be unique across kinds.
+    const NAME: &'static str;
+
+    /// Apply this event to the entity it happened to during playback.
+    fn apply(&self, _state: &mut EntityState) {}
+}
+
+/// Playback hook for a registered custom kind.
+pub type CustomHandler = fn(&ChronoEvent, &mut EntityState);
+
+/// Decode and apply a `Custom` event as `E`. Undecodable payloads are skipped.
+pub(crate) fn apply_custom<E: CustomEvent>(e: &ChronoEvent, state: &mut EntityState) {
+    if let Some(Ok(data)) = e.as_custom::<E>() {
+        data.apply(state);
+    }
+}
@@ -0,0 +1,102 @@
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+use crate::{ChronoEvent, EntityState, Timeline, UvoxId};
+
+/// Result of [`Timeline::diff`], from the old timeline to the new one.
+#[derive(Debug, Clone, Default)]
+pub struct TimelineDiff {
+    /// Events only in the new timeline.
+    pub added: Vec<ChronoEvent>,
+    /// Events only in the old timeline.
+    pub removed: Vec<ChronoEvent>,
+    /// Matched events whose content changed, as `(old, new)`.
+    pub modified: Vec<(ChronoEvent, ChronoEvent)>,
+    /// Entities whose final playback state differs.
+    pub state_changes: Vec<StateChange>,
+}
+
+/// An entity's final state before and after; `None` if it doesn't exist on that side.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateChange {
+    pub id: UvoxId,
+    pub before: Option<EntityState>,
+    pub after: Option<EntityState>,
+}
+
+impl TimelineDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.modified.is_empty()
+            && self.state_changes.is_empty()
+    }
+}
+
+/// Fallback identity for events whose ids don't line up (e.g. the same
+/// reading imported twice): same tick, same entity, same kind.
+type EventKey = (i64, UvoxId, String);
+
+fn event_key(e: &ChronoEvent) -> EventKey {
+    (e.t.ticks("nanoseconds"), e.id, e.kind.name().to_string())
+}
+
+impl Timeline {
+    /// Compare this (old) timeline against `other` (new). Events are matched
+    /// by `event_id` first, then by tick + entity + kind.
+    pub fn diff(&self, other: &Timeline) -> TimelineDiff {
+        let mut diff = TimelineDiff::default();
+
+        let new_by_id: HashMap<Uuid, &ChronoEvent> = other.events.iter().map(|e| (e.event_id, e)).collect();
+        let mut matched_new: HashSet<Uuid> = HashSet::new();
+        let mut unmatched_old = Vec::new();
+        for old in &self.events {
+            match new_by_id.get(&old.event_id) {
+                Some(new) => {
+                    matched_new.insert(new.event_id);
+                    if !old.same_content(new) {
+                        diff.modified.push((old.clone(), (*new).clone()));
+                    }
+                }
+                None => unmatched_old.push(old),
+            }
+        }
+
+        let mut new_by_key: HashMap<EventKey, Vec<&ChronoEvent>> = HashMap::new();
+        for new in other.events.iter().filter(|e| !matched_new.contains(&e.event_id)) {
+            new_by_key.entry(event_key(new)).or_default().push(new);
+        }
+        for old in unmatched_old {
+            let candidates = new_by_key.get_mut(&event_key(old)).filter(|c| !c.is_empty());
+            match candidates {
+                Some(candidates) => {
+                    let new = candidates.remove(0);
+                    matched_new.insert(new.event_id);
+                    if !old.same_content(new) {
+                        diff.modified.push((old.clone(), new.clone()));
+                    }
+                }
+                None => diff.removed.push(old.clone()),
+            }
+        }
+
+        diff.added = other
+            .events
+            .iter()
+            .filter(|e| !matched_new.contains(&e.event_id))
+            .cloned()
+            .collect();
+
+        let before = self.playback();
+        let mut after = other.playback();
+        for (id, old_state) in before {
+            let new_state = after.remove(&id);
+            if new_state.as_ref() != Some(&old_state) {
+                diff.state_changes.push(StateChange { id, before: Some(old_state), after: new_state });
+            }
+        }
+        diff.state_changes.extend(after.into_iter().map(|(id, s)| StateChange { id, before: None, after: Some(s) }));
+        diff.state_changes.sort_by_key(|c| c.id.as_tuple());
+
+        diff
+    }
+}
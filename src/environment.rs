@@ -0,0 +1,58 @@
+use serde::{Serialize, Deserialize};
+use crate::Cartesian;
+
+/// Ambient conditions at a location.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Environment {
+    /// Ambient temperature (°C).
+    pub temperature: f64,
+    /// Ambient pressure (Pascals).
+    pub pressure: f64,
+}
+
+impl Environment {
+    /// Room temperature at 1 atm.
+    pub const STANDARD: Environment = Environment { temperature: 20.0, pressure: 101_325.0 };
+    /// Deep space: cosmic background temperature, no pressure.
+    pub const VACUUM: Environment = Environment { temperature: -270.45, pressure: 0.0 };
+
+    pub fn new(temperature: f64, pressure: f64) -> Self {
+        Self { temperature, pressure }
+    }
+}
+
+impl Default for Environment {
+    fn default() -> Self {
+        Self::STANDARD
+    }
+}
+
+/// A volume of space inside a reference frame.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Region {
+    /// Everything in a frame.
+    Frame(u64),
+    /// A spherical shell around the frame center, by radial distance in µm.
+    Shell { frame_id: u64, min_r_um: u64, max_r_um: u64 },
+    /// An axis-aligned box in the frame's Cartesian coordinates (meters).
+    Box { frame_id: u64, min: Cartesian, max: Cartesian },
+}
+
+impl Region {
+    /// Whether a Cartesian position (meters) in `frame_id` lies inside the region.
+    pub fn contains(&self, frame_id: u64, pos: &Cartesian) -> bool {
+        match self {
+            Region::Frame(f) => *f == frame_id,
+            Region::Shell { frame_id: f, min_r_um, max_r_um } => {
+                let r_um = (pos.x * pos.x + pos.y * pos.y + pos.z * pos.z).sqrt() * 1e6;
+                *f == frame_id && r_um >= *min_r_um as f64 && r_um <= *max_r_um as f64
+            }
+            Region::Box { frame_id: f, min, max } => {
+                *f == frame_id
+                    && (min.x..=max.x).contains(&pos.x)
+                    && (min.y..=max.y).contains(&pos.y)
+                    && (min.z..=max.z).contains(&pos.z)
+            }
+        }
+    }
+}
@@ -1,25 +1,172 @@
 use thiserror::Error;
+use uuid::Uuid;
+#[cfg(feature = "supabase")]
 use supabasic::error::SupabasicError;
 
 #[derive(Debug, Error)]
 pub enum ChronovoxError {
+    /// A database error that fits none of the variants below.
     #[error("Supabase error: {0}")]
     Db(String),
 
+    #[cfg(feature = "supabase")]
     #[error("HTTP error: {0}")]
     Http(#[from] reqwest::Error),
 
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
 
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// A Postgres error that fits none of the variants below.
+    #[cfg(feature = "postgres")]
+    #[error("Postgres error: {0}")]
+    Postgres(tokio_postgres::Error),
+
     #[error("Missing field: {0}")]
     MissingField(String),
+
+    #[error("Unsupported event version: {0}")]
+    UnsupportedVersion(u32),
+
+    #[error("Invalid {kind} payload: {}", violations.join("; "))]
+    InvalidPayload { kind: String, violations: Vec<String> },
+
+    #[error("Not found: {0}")]
+    NotFound(String),
+
+    /// The write collides with stored data (e.g. a duplicate key).
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    /// Missing or rejected credentials, or insufficient privileges.
+    #[error("Not authorized: {0}")]
+    Unauthorized(String),
+
+    #[error("Rate limited: {0}")]
+    RateLimited(String),
+
+    /// The request didn't get through, or the database was momentarily
+    /// unavailable (connection refused, timeout, deadlock, ...).
+    #[error("Network error: {0}")]
+    Network(String),
+
+    /// The table or a column chronovox expects doesn't exist.
+    #[error("Schema mismatch: {0}")]
+    SchemaMismatch(String),
+
+    /// Streaming playback met a retraction or correction (this event id)
+    /// without the history needed to apply it.
+    #[error("Event {0} amends earlier events; follow the stream with history to apply it")]
+    UnresolvedAmendment(Uuid),
+
+    /// A stored row couldn't be turned back into an event.
+    #[error("Could not decode row {}: {reason}", row_id.map_or_else(|| "?".to_string(), |id| id.to_string()))]
+    Decode { row_id: Option<Uuid>, reason: String },
+}
+
+impl ChronovoxError {
+    /// Whether the same call may succeed if simply tried again later.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ChronovoxError::Network(_) | ChronovoxError::RateLimited(_) => true,
+            #[cfg(feature = "supabase")]
+            ChronovoxError::Http(e) => e.is_timeout() || e.is_connect(),
+            _ => false,
+        }
+    }
+}
+
+/// Connection exceptions, serialization failures, deadlocks, resource
+/// exhaustion, query cancellation and shutdowns.
+#[cfg(any(feature = "supabase", feature = "postgres"))]
+fn is_transient_sqlstate(code: &str) -> bool {
+    matches!(code, "40001" | "40P01" | "57014" | "57P01" | "57P02" | "57P03")
+        || code.starts_with("08")
+        || code.starts_with("53")
+}
+
+/// The variant for a Postgres SQLSTATE, shared by both backends so callers
+/// see the same errors from either.
+#[cfg(any(feature = "supabase", feature = "postgres"))]
+fn sqlstate_variant(code: &str) -> Option<fn(String) -> ChronovoxError> {
+    match code {
+        "23505" => Some(ChronovoxError::Conflict),
+        "42P01" | "42703" => Some(ChronovoxError::SchemaMismatch),
+        "28000" | "28P01" | "42501" => Some(ChronovoxError::Unauthorized),
+        code if is_transient_sqlstate(code) => Some(ChronovoxError::Network),
+        _ => None,
+    }
+}
+
+/// Classify an error body returned by PostgREST (or the Supabase gateway
+/// in front of it), which carries a Postgres SQLSTATE or `PGRST` code.
+/// supabasic doesn't expose the HTTP status, so gateway bodies are
+/// recognised by the status or error code some of them repeat.
+#[cfg(feature = "supabase")]
+pub(crate) fn from_postgrest(body: &serde_json::Value) -> ChronovoxError {
+    let text = |key: &str| body.get(key).and_then(|v| v.as_str());
+    let code = text("code").or_else(|| text("error_code")).unwrap_or_default();
+    let status = ["status", "statusCode", "code"]
+        .iter()
+        .find_map(|key| body.get(*key).and_then(|v| v.as_u64().or_else(|| v.as_str()?.parse().ok())))
+        .filter(|status| (100..600).contains(status));
+    let message = text("message")
+        .or_else(|| text("error"))
+        .map_or_else(|| body.to_string(), str::to_string);
+    let lower = message.to_lowercase();
+
+    if let Some(variant) = sqlstate_variant(code) {
+        return variant(message);
+    }
+    match (code, status) {
+        (_, Some(429)) | ("over_request_rate_limit" | "too_many_requests", _) => ChronovoxError::RateLimited(message),
+        ("PGRST116", _) | (_, Some(404)) => ChronovoxError::NotFound(message),
+        ("PGRST204" | "PGRST205", _) => ChronovoxError::SchemaMismatch(message),
+        ("PGRST301" | "PGRST302", _) | (_, Some(401 | 403)) => ChronovoxError::Unauthorized(message),
+        ("PGRST000" | "PGRST001" | "PGRST002" | "PGRST003", _) | (_, Some(500..=599)) => {
+            ChronovoxError::Network(message)
+        }
+        _ if lower.contains("rate limit") || lower.contains("too many requests") => {
+            ChronovoxError::RateLimited(message)
+        }
+        _ if lower.contains("api key") || lower.contains("jwt") => ChronovoxError::Unauthorized(message),
+        _ => ChronovoxError::Db(message),
+    }
 }
 
 // unify everything under Chronovox
+// (supabasic links its own reqwest, so its errors can't become `Http`)
+#[cfg(feature = "supabase")]
 impl From<SupabasicError> for ChronovoxError {
     fn from(err: SupabasicError) -> Self {
-        ChronovoxError::Db(err.to_string())
+        match err {
+            SupabasicError::Http(e) if e.is_connect() || e.is_timeout() => ChronovoxError::Network(e.to_string()),
+            // PostgREST always answers in JSON; anything else is a proxy or
+            // gateway page. supabasic drops the status and body, so a 502
+            // can't be told from a 413 and neither is assumed transient.
+            SupabasicError::Http(e) if e.is_decode() => {
+                ChronovoxError::Db(format!("response was not JSON (proxy or gateway error?): {e}"))
+            }
+            SupabasicError::Json(e) => ChronovoxError::Json(e),
+            other => ChronovoxError::Db(other.to_string()),
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl From<tokio_postgres::Error> for ChronovoxError {
+    fn from(err: tokio_postgres::Error) -> Self {
+        let message = || err.as_db_error().map_or_else(|| err.to_string(), |db| db.message().to_string());
+        if let Some(variant) = err.code().and_then(|code| sqlstate_variant(code.code())) {
+            return variant(message());
+        }
+        let io = std::error::Error::source(&err).is_some_and(|source| source.is::<std::io::Error>());
+        if err.is_closed() || io {
+            return ChronovoxError::Network(message());
+        }
+        ChronovoxError::Postgres(err)
     }
 }
 
@@ -1,18 +1,37 @@
-use crate::{UvoxId, TimeDelta, Cartesian};
+use crate::{UvoxId, TimeDelta, Cartesian, Region, Condition, Thresholds, CustomEvent, PayloadSchemas, Result, EVENT_VERSION};
+use chrono::{DateTime, Utc};
 use serde::{Serialize, Deserialize};
+use std::collections::HashMap;
 use uuid::Uuid;
 
 /// A Chronovox event: something happening at a place + time.
+///
+/// Deserialization upcasts older versions (see [`upcast`](crate::upcast)).
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "serde_json::Value")]
 pub struct ChronoEvent {
+    /// Schema version of this representation.
+    pub version: u32,
+    /// Unique identity of this event.
+    pub event_id: Uuid,
+    /// The event that caused this one, if any.
+    pub causation_id: Option<Uuid>,
+    /// The operation this event belongs to; shared along a causal chain.
+    pub correlation_id: Option<Uuid>,
     /// Where it occurred (spatial ID).
     pub id: UvoxId,
     /// When it occurred (time delta from epoch).
     pub t: TimeDelta,
+    /// When it was recorded, i.e. when the system learned of it. Stored
+    /// events carry the database's insert time instead.
+    pub recorded_at: DateTime<Utc>,
+    /// System or device that reported it (e.g. a sensor id).
+    pub source: Option<String>,
+    /// Person or service that recorded it.
+    pub author: Option<String>,
     /// What happened.
     pub kind: EventKind,
     /// Optional extra data (system-specific).
-    #[serde(default)]
     pub payload: Option<serde_json::Value>,
 }
 
@@ -23,7 +42,8 @@ pub struct ChronoEvent {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum EventKind {
     // === Core Lifecycle ===
-    /// An object/entity comes into existence.
+    /// An object/entity comes into existence at the event's location.
+    /// Initial state overrides may be carried in the payload (see [`SpawnState`]).
     Spawn,
     /// An object/entity ceases to exist.
     Despawn,
@@ -43,6 +63,14 @@ pub enum EventKind {
     Radiation { dose: f64 },
     /// Sudden impact/shock (acceleration in g).
     Shock { g: f64 },
+    /// Ambient conditions over a region, applied to every entity inside it
+    /// from this event until `until` (or indefinitely).
+    Ambient {
+        region: Region,
+        temperature_c: Option<f64>,
+        pressure_pa: Option<f64>,
+        until: Option<TimeDelta>,
+    },
 
     // === Material / Integrity ===
     /// Generic degradation over time.
@@ -64,24 +92,206 @@ pub enum EventKind {
         amount: f64,
     },
 
+    // === Audit ===
+    /// The event `event_id` was wrong; playback skips it.
+    Retract { event_id: Uuid },
+    /// The event `event_id` was wrong; playback applies `replacement` instead.
+    Correct { event_id: Uuid, replacement: Box<ChronoEvent> },
+
+    // === Derived ===
+    /// An entity's condition crossed a threshold. Emitted by playback, ignored when replayed.
+    ConditionChange { from: Condition, to: Condition },
+
     // === Wild Card ===
     /// Catch-all for events not yet modeled in the vocabulary.
+    /// Typed downstream kinds use this with their [`CustomEvent::NAME`].
     Custom(String),
 }
 
+impl EventKind {
+    /// Variant name (e.g. `"Move"`), or the tag itself for `Custom`.
+    pub fn name(&self) -> &str {
+        match self {
+            EventKind::Spawn => "Spawn",
+            EventKind::Despawn => "Despawn",
+            EventKind::Move { .. } => "Move",
+            EventKind::Teleport { .. } => "Teleport",
+            EventKind::TemperatureChange { .. } => "TemperatureChange",
+            EventKind::PressureChange { .. } => "PressureChange",
+            EventKind::Radiation { .. } => "Radiation",
+            EventKind::Shock { .. } => "Shock",
+            EventKind::Ambient { .. } => "Ambient",
+            EventKind::Degrade { .. } => "Degrade",
+            EventKind::Leak { .. } => "Leak",
+            EventKind::Fracture { .. } => "Fracture",
+            EventKind::Bond { .. } => "Bond",
+            EventKind::Unbond { .. } => "Unbond",
+            EventKind::Transfer { .. } => "Transfer",
+            EventKind::Retract { .. } => "Retract",
+            EventKind::Correct { .. } => "Correct",
+            EventKind::ConditionChange { .. } => "ConditionChange",
+            EventKind::Custom(name) => name,
+        }
+    }
+}
+
+/// Initial state overrides for a `Spawn` event, read from its payload.
+///
+/// Anything left unset falls back to the playback defaults
+/// (position derived from the event's `UvoxId`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SpawnState {
+    /// Initial position, overriding the one derived from the `UvoxId`.
+    pub pos: Option<Cartesian>,
+    /// Initial temperature (°C).
+    pub temperature: Option<f64>,
+    /// Initial pressure (Pascals).
+    pub pressure: Option<f64>,
+    /// Thermal time constant (seconds) for relaxation toward ambient.
+    pub thermal_tau_s: Option<f64>,
+    /// Material name, used to look up thresholds in the playback config.
+    pub material: Option<String>,
+    /// Per-entity thresholds, overriding the material's.
+    pub thresholds: Option<Thresholds>,
+    /// Arbitrary system-specific fields copied onto the entity.
+    #[serde(default)]
+    pub fields: HashMap<String, serde_json::Value>,
+}
+
+impl SpawnState {
+    /// Encode as a `Spawn` payload.
+    pub fn to_payload(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+}
+
 impl ChronoEvent {
+    pub fn new(id: UvoxId, t: TimeDelta, kind: EventKind) -> Self {
+        Self {
+            version: EVENT_VERSION,
+            event_id: Uuid::new_v4(),
+            causation_id: None,
+            correlation_id: None,
+            id,
+            t,
+            recorded_at: Utc::now(),
+            source: None,
+            author: None,
+            kind,
+            payload: None,
+        }
+    }
+
+    pub fn with_payload(mut self, payload: serde_json::Value) -> Self {
+        self.payload = Some(payload);
+        self
+    }
+
+    pub fn with_recorded_at(mut self, recorded_at: DateTime<Utc>) -> Self {
+        self.recorded_at = recorded_at;
+        self
+    }
+
+    pub fn with_source(mut self, source: impl Into<String>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    pub fn with_author(mut self, author: impl Into<String>) -> Self {
+        self.author = Some(author.into());
+        self
+    }
+
+    pub fn with_correlation(mut self, correlation_id: Uuid) -> Self {
+        self.correlation_id = Some(correlation_id);
+        self
+    }
+
+    /// Mark this event as caused by `cause`, joining its correlation
+    /// (or starting one at `cause` if it had none).
+    pub fn caused_by(mut self, cause: &ChronoEvent) -> Self {
+        self.causation_id = Some(cause.event_id);
+        self.correlation_id = Some(cause.correlation_id.unwrap_or(cause.event_id));
+        self
+    }
+
+    /// Retract `original` at time `t`, keeping it in the history.
+    pub fn retraction_of(original: &ChronoEvent, t: TimeDelta) -> Self {
+        Self::new(original.id, t, EventKind::Retract { event_id: original.event_id }).caused_by(original)
+    }
+
+    /// Replace `original` with `replacement` at time `t`, keeping both in the history.
+    pub fn correction_of(original: &ChronoEvent, t: TimeDelta, replacement: ChronoEvent) -> Self {
+        let kind = EventKind::Correct {
+            event_id: original.event_id,
+            replacement: Box::new(replacement),
+        };
+        Self::new(original.id, t, kind).caused_by(original)
+    }
+
+    /// Whether `other` says the same thing at the same place and time,
+    /// ignoring identity and provenance.
+    pub fn same_content(&self, other: &ChronoEvent) -> bool {
+        self.id == other.id
+            && self.t.ticks("nanoseconds") == other.t.ticks("nanoseconds")
+            && self.payload == other.payload
+            && serde_json::to_value(&self.kind).ok() == serde_json::to_value(&other.kind).ok()
+    }
+
+    /// The event this retracts or corrects, if it is a retraction or correction.
+    pub fn amends(&self) -> Option<Uuid> {
+        match &self.kind {
+            EventKind::Retract { event_id } | EventKind::Correct { event_id, .. } => Some(*event_id),
+            _ => None,
+        }
+    }
+
+    /// Build a typed custom event: `Custom(E::NAME)` with `data` as the payload.
+    pub fn custom<E: CustomEvent>(id: UvoxId, t: TimeDelta, data: &E) -> Result<Self> {
+        Ok(Self::new(id, t, EventKind::Custom(E::NAME.to_string())).with_payload(serde_json::to_value(data)?))
+    }
+
+    /// Check the payload against `schemas`, passing the event through if valid.
+    pub fn validated(self, schemas: &PayloadSchemas) -> Result<Self> {
+        schemas.check(&self)?;
+        Ok(self)
+    }
+
+    /// Decode as the typed custom kind `E`, or `None` if this is some other kind.
+    pub fn as_custom<E: CustomEvent>(&self) -> Option<Result<E>> {
+        match &self.kind {
+            EventKind::Custom(name) if name == E::NAME => {
+                let payload = self.payload.clone().unwrap_or_default();
+                Some(serde_json::from_value(payload).map_err(Into::into))
+            }
+            _ => None,
+        }
+    }
+
+    /// Initial state overrides if this is a `Spawn` carrying a [`SpawnState`] payload.
+    ///
+    /// Payloads that don't match the shape are ignored.
+    pub fn spawn_state(&self) -> Option<SpawnState> {
+        match self.kind {
+            EventKind::Spawn => self
+                .payload
+                .as_ref()
+                .and_then(|p| serde_json::from_value(p.clone()).ok()),
+            _ => None,
+        }
+    }
+
     /// A simple placeholder for testing
     pub fn dummy() -> Self {
-        Self {
-            id: UvoxId {
+        Self::new(
+            UvoxId {
                 frame_id: 1,
                 r_um: 0,
                 lat_code: 0,
                 lon_code: 0,
             },
-            t: TimeDelta::from_now(),
-            kind: EventKind::Spawn,
-            payload: None,
-        }
+            TimeDelta::from_now(),
+            EventKind::Spawn,
+        )
     }
 }
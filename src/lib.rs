@@ -3,11 +3,53 @@ pub use tdt::core::TimeDelta;
 pub use uvoxxyz::types::Cartesian;
 
 pub mod error;
+#[cfg(feature = "supabase")]
 pub mod persist;
+pub mod query;
+pub mod table;
+#[cfg(any(feature = "supabase", feature = "postgres"))]
+mod row;
 pub mod event;
 pub mod timeline;
+pub mod environment;
+pub mod playback;
+pub mod condition;
+pub mod custom;
+pub mod schema;
+pub mod version;
+pub mod merge;
+pub mod diff;
+pub mod compact;
+#[cfg(feature = "supabase")]
+pub mod subscription;
+#[cfg(feature = "supabase")]
+pub mod outbox;
+#[cfg(feature = "postgres")]
+pub mod postgres;
 
 pub use error::{ChronovoxError, Result};
-pub use persist::{insert_event_for_entity, fetch_events_for_entity};
-pub use event::{ChronoEvent, EventKind};
+#[cfg(feature = "supabase")]
+pub use persist::{
+    insert_event_for_entity, insert_events, fetch_events_for_entity, fetch_events_for_entity_with,
+    fetch_events_for_entities, fetch_events_for_entities_with, fetch_events_page,
+    fetch_events_page_with, fetch_events_in_range, stream_events_for_entity,
+    stream_events_for_entity_with,
+};
+pub use query::{EventQuery, EventPage, PageCursor};
+pub use table::EventTable;
+pub use event::{ChronoEvent, EventKind, SpawnState};
 pub use timeline::{Timeline, EntityState};
+pub use environment::{Environment, Region};
+pub use playback::{Playback, PlaybackConfig};
+pub use condition::{Condition, Thresholds};
+pub use custom::{CustomEvent, CustomHandler};
+pub use schema::{PayloadSchemas, PayloadValidator};
+pub use version::{EVENT_VERSION, upcast};
+pub use merge::{MergeConflict, MergeReport};
+pub use diff::{StateChange, TimelineDiff};
+#[cfg(feature = "supabase")]
+pub use subscription::subscribe_to_entity;
+#[cfg(feature = "supabase")]
+pub use outbox::{Backoff, Outbox, OutboxEntry, SyncStatus};
+#[cfg(feature = "postgres")]
+pub use postgres::PgEventStore;
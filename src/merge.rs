@@ -0,0 +1,119 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use uuid::Uuid;
+use crate::{ChronoEvent, EventKind, Timeline, UvoxId};
+
+/// Result of [`Timeline::merge`].
+#[derive(Debug, Clone, Default)]
+pub struct MergeReport {
+    /// All input events in chronological order, ties broken by input order.
+    pub timeline: Timeline,
+    /// Events that contradict each other; all of them are kept in `timeline`.
+    pub conflicts: Vec<MergeConflict>,
+    /// Events dropped because an earlier input already had the same `event_id`.
+    pub duplicates: usize,
+}
+
+/// Events from different inputs that set the same entity's state differently
+/// at the same tick (e.g. two `Teleport`s to different positions).
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeConflict {
+    pub id: UvoxId,
+    pub t_ns: i64,
+    /// `(input index, event id)` of every event involved.
+    pub events: Vec<(usize, Uuid)>,
+}
+
+impl Timeline {
+    /// K-way merge of timelines that are each already in chronological order
+    /// (unsorted inputs are sorted first), reporting conflicts instead of
+    /// silently picking a winner.
+    pub fn merge(timelines: impl IntoIterator<Item = Timeline>) -> MergeReport {
+        let mut inputs: Vec<std::vec::IntoIter<ChronoEvent>> = Vec::new();
+        let mut schemas = None;
+        for mut timeline in timelines {
+            if !timeline.events.is_sorted() {
+                timeline.events.sort();
+            }
+            schemas = schemas.or(timeline.schemas);
+            inputs.push(timeline.events.into_iter());
+        }
+
+        let mut heap = BinaryHeap::new();
+        let mut heads: Vec<Option<ChronoEvent>> = Vec::with_capacity(inputs.len());
+        for (source, input) in inputs.iter_mut().enumerate() {
+            let head = input.next();
+            if let Some(e) = &head {
+                heap.push(Reverse((e.t.ticks("nanoseconds"), source)));
+            }
+            heads.push(head);
+        }
+
+        let mut report = MergeReport::default();
+        let mut seen = HashSet::new();
+        let mut tick_group: Vec<(usize, usize)> = Vec::new(); // (source, index in merged)
+        let mut group_tick = None;
+
+        while let Some(Reverse((tick, source))) = heap.pop() {
+            let Some(e) = heads[source].take() else { continue };
+            if let Some(next) = inputs[source].next() {
+                heap.push(Reverse((next.t.ticks("nanoseconds"), source)));
+                heads[source] = Some(next);
+            }
+
+            if !seen.insert(e.event_id) {
+                report.duplicates += 1;
+                continue;
+            }
+            if group_tick != Some(tick) {
+                report.conflicts.extend(find_conflicts(&report.timeline.events, &tick_group));
+                tick_group.clear();
+                group_tick = Some(tick);
+            }
+            tick_group.push((source, report.timeline.events.len()));
+            report.timeline.events.push(e);
+        }
+        report.conflicts.extend(find_conflicts(&report.timeline.events, &tick_group));
+        report.timeline.schemas = schemas;
+        report
+    }
+}
+
+/// Kinds that set state outright, so two differing ones at the same tick contradict.
+fn is_absolute(kind: &EventKind) -> bool {
+    matches!(kind, EventKind::Spawn | EventKind::Despawn | EventKind::Teleport { .. })
+}
+
+/// Conflicts among events sharing one tick.
+fn find_conflicts(events: &[ChronoEvent], group: &[(usize, usize)]) -> Vec<MergeConflict> {
+    let mut by_entity: HashMap<UvoxId, Vec<(usize, &ChronoEvent)>> = HashMap::new();
+    for &(source, index) in group {
+        let e = &events[index];
+        if is_absolute(&e.kind) {
+            by_entity.entry(e.id).or_default().push((source, e));
+        }
+    }
+
+    let mut conflicts: Vec<MergeConflict> = by_entity
+        .into_iter()
+        .filter(|(_, candidates)| {
+            let sources: HashSet<usize> = candidates.iter().map(|(s, _)| *s).collect();
+            let contents: HashSet<String> = candidates.iter().map(|(_, e)| content_key(e)).collect();
+            sources.len() > 1 && contents.len() > 1
+        })
+        .map(|(id, candidates)| MergeConflict {
+            id,
+            t_ns: candidates[0].1.t.ticks("nanoseconds"),
+            events: candidates.iter().map(|(s, e)| (*s, e.event_id)).collect(),
+        })
+        .collect();
+    conflicts.sort_by_key(|c| c.id.as_tuple());
+    conflicts
+}
+
+/// What an event says, ignoring its identity and provenance.
+fn content_key(e: &ChronoEvent) -> String {
+    let kind = serde_json::to_string(&e.kind).unwrap_or_default();
+    let payload = e.payload.as_ref().map(|p| p.to_string()).unwrap_or_default();
+    format!("{kind}|{payload}")
+}
@@ -0,0 +1,179 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use supabasic::Supabase;
+use uuid::Uuid;
+use crate::{ChronoEvent, EventTable, Result};
+
+/// Exponential retry delay: `base * 2^(attempts - 1)`, capped at `max`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Backoff {
+    pub base: Duration,
+    pub max: Duration,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self { base: Duration::from_secs(1), max: Duration::from_secs(300) }
+    }
+}
+
+impl Backoff {
+    pub fn delay(&self, attempts: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempts.saturating_sub(1));
+        self.base.saturating_mul(factor).min(self.max)
+    }
+}
+
+/// An event waiting to be written.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutboxEntry {
+    pub entity_id: Uuid,
+    pub event: ChronoEvent,
+    /// Failed attempts so far.
+    pub attempts: u32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    /// Set when the last failure can't be fixed by retrying (bad
+    /// credentials, missing table, ...). Parked entries are skipped by
+    /// [`Outbox::sync`] until [`Outbox::unpark`] is called.
+    #[serde(default)]
+    pub parked: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SyncStatus {
+    /// Entries still queued for retry.
+    pub pending: usize,
+    /// Entries set aside after a non-retryable failure.
+    pub parked: usize,
+    /// Entries written by this outbox since it was opened.
+    pub synced: usize,
+    /// When the earliest pending entry is due.
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    /// Most recent failure among queued entries, parked or not.
+    pub last_error: Option<String>,
+}
+
+/// Durable local queue of events to write once Supabase is reachable.
+///
+/// The queue lives in a JSON file rewritten on every change, so events
+/// survive restarts. Rows are keyed by each event's own `event_id`, so
+/// replaying an entry can't create a second row.
+#[derive(Debug)]
+pub struct Outbox {
+    path: PathBuf,
+    entries: Vec<OutboxEntry>,
+    backoff: Backoff,
+    table: EventTable,
+    synced: usize,
+}
+
+impl Outbox {
+    /// Open the outbox stored at `path`, creating it empty if missing.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let entries = match std::fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Self { path, entries, backoff: Backoff::default(), table: EventTable::default(), synced: 0 })
+    }
+
+    pub fn with_backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Write to `table` instead of the default `events`.
+    pub fn with_table(mut self, table: EventTable) -> Self {
+        self.table = table;
+        self
+    }
+
+    /// Queue an event, due immediately.
+    pub fn enqueue(&mut self, entity_id: Uuid, event: ChronoEvent) -> Result<()> {
+        self.entries.push(OutboxEntry {
+            entity_id,
+            event,
+            attempts: 0,
+            next_attempt_at: Utc::now(),
+            last_error: None,
+            parked: false,
+        });
+        self.save()
+    }
+
+    pub fn pending(&self) -> &[OutboxEntry] {
+        &self.entries
+    }
+
+    pub fn status(&self) -> SyncStatus {
+        let parked = self.entries.iter().filter(|e| e.parked).count();
+        SyncStatus {
+            pending: self.entries.len() - parked,
+            parked,
+            synced: self.synced,
+            next_attempt_at: self.entries.iter().filter(|e| !e.parked).map(|e| e.next_attempt_at).min(),
+            last_error: self
+                .entries
+                .iter()
+                .filter(|e| e.last_error.is_some())
+                .max_by_key(|e| e.next_attempt_at)
+                .and_then(|e| e.last_error.clone()),
+        }
+    }
+
+    /// Make parked entries due again, e.g. once credentials or the schema
+    /// have been fixed.
+    pub fn unpark(&mut self) -> Result<()> {
+        let now = Utc::now();
+        for entry in self.entries.iter_mut().filter(|e| e.parked) {
+            entry.parked = false;
+            entry.next_attempt_at = now;
+        }
+        self.save()
+    }
+
+    /// Try every due entry in queue order. Written entries leave the queue;
+    /// [retryable](crate::ChronovoxError::is_retryable) failures are
+    /// rescheduled with backoff and the rest are parked. Only failing to
+    /// save the queue itself is an error.
+    ///
+    /// An entry whose earlier attempt was stored even though the call
+    /// failed (e.g. the response timed out) counts as written: inserts skip
+    /// events already stored rather than failing on the duplicate key.
+    pub async fn sync(&mut self, supa: &Supabase) -> Result<SyncStatus> {
+        let now = Utc::now();
+        let mut remaining = Vec::with_capacity(self.entries.len());
+        for mut entry in std::mem::take(&mut self.entries) {
+            if entry.parked || entry.next_attempt_at > now {
+                remaining.push(entry);
+                continue;
+            }
+            match self.table.insert_events(supa, entry.entity_id, [&entry.event]).await {
+                Ok(_) => self.synced += 1,
+                Err(err) => {
+                    entry.attempts += 1;
+                    entry.last_error = Some(err.to_string());
+                    entry.parked = !err.is_retryable();
+                    entry.next_attempt_at = Utc::now() + self.backoff.delay(entry.attempts);
+                    remaining.push(entry);
+                }
+            }
+        }
+        self.entries = remaining;
+        self.save()?;
+        Ok(self.status())
+    }
+
+    /// Write the queue atomically (temp file, then rename).
+    fn save(&self) -> Result<()> {
+        let tmp = self.path.with_extension("tmp");
+        std::fs::write(&tmp, serde_json::to_vec(&self.entries)?)?;
+        std::fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
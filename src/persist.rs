@@ -1,100 +1,410 @@
+use std::collections::{HashMap, HashSet};
 use crate::Result; // Chronovox’s Result<T>
-use crate::error::ChronovoxError;
+use crate::error::{from_postgrest, ChronovoxError};
 use supabasic::Supabase;
 use uuid::Uuid;
-use serde_json::json;
+use serde_json::Value;
+use futures::{stream, Stream, TryStreamExt};
 
-use crate::{Timeline, ChronoEvent, EventKind};
-use uvoxid::UvoxId;
-use tdt::core::TimeDelta;
+use crate::{Timeline, ChronoEvent, PayloadSchemas, EventQuery, EventPage, PageCursor};
+use crate::row::{decode_row, event_row, EventRowDb};
+use crate::table::EventTable;
 
+/// Append an event to an entity's history. Rows are never updated or
+/// deleted; retractions and corrections are appended like any other event.
+///
+/// The event's `event_id` is the row's primary key: inserting an event that
+/// is already stored does nothing and returns its id, so retries are safe.
 pub async fn insert_event_for_entity(
     supa: &Supabase,
     entity_id: Uuid,
     event: &ChronoEvent,
 ) -> Result<Uuid> {
-    let event_val = json!({
-        "entity_id": entity_id,
-        "frame_id": event.id.frame_id as i64,
-        "r_um": event.id.r_um as i64,
-        "lat_code": event.id.lat_code as i64,
-        "lon_code": event.id.lon_code as i64,
-        "ticks": event.t.ticks("nanoseconds"),
-        "timestamp": chrono::Utc::now(),
-        "kind": format!("{:?}", event.kind),
-        "move_offset": event.payload, // TODO: maybe split later
-        "payload": event.payload,
-    });
-
-    let inserted: Vec<serde_json::Value> = supa
-        .from("events")
-        .insert(json!([event_val]))
-        .select("id")
-        .execute_typed()
+    insert_events(supa, entity_id, [event])
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| ChronovoxError::MissingField("id".into()))
+}
+
+/// Events sent per request by [`insert_events`].
+pub const INSERT_BATCH_SIZE: usize = 500;
+
+/// Append many events (a slice, a `&Timeline`, ...) to an entity's history,
+/// [`INSERT_BATCH_SIZE`] rows per request. Returns the ids in input order.
+/// Batches already sent stay inserted if a later one fails. Events already
+/// stored are skipped (their ids are still returned), so a failed call can
+/// simply be repeated.
+pub async fn insert_events<'a>(
+    supa: &Supabase,
+    entity_id: Uuid,
+    events: impl IntoIterator<Item = &'a ChronoEvent>,
+) -> Result<Vec<Uuid>> {
+    EventTable::default().insert_events(supa, entity_id, events).await
+}
+
+/// Columns read back into [`EventRowDb`].
+const ROW_COLUMNS: &[&str] = &[
+    "id", "frame_id", "r_um", "lat_code", "lon_code", "version", "causation_id", "correlation_id",
+    "ticks", "timestamp", "source", "author", "kind", "kind_data", "move_offset", "payload",
+];
+
+/// Postgres `unique_violation`.
+const UNIQUE_VIOLATION: &str = "23505";
+
+/// Insert rows in one request, returning their ids, or `None` if any of
+/// them is already stored.
+async fn post_rows(supa: &Supabase, table: &EventTable, rows: &[Value]) -> Result<Option<Vec<Uuid>>> {
+    let stored = rows.iter().map(|row| table.store_row(row.clone())).collect();
+    let response = supa
+        .from(&table.name)
+        .insert(Value::Array(stored)) // `select` would turn this back into a GET
+        .execute()
         .await?;
 
-    println!("DEBUG inserted = {:?}", inserted);
+    match response {
+        Value::Array(inserted) if inserted.len() == rows.len() => {
+            let id = table.column("id");
+            inserted.iter().map(|row| row_id(row, id)).collect::<Result<Vec<_>>>().map(Some)
+        }
+        Value::Array(_) => Err(ChronovoxError::MissingField("id".into())),
+        Value::Object(err) if err.get("code").and_then(Value::as_str) == Some(UNIQUE_VIOLATION) => Ok(None),
+        other => Err(from_postgrest(&other)),
+    }
+}
 
-    let event_id = inserted
-        .get(0)
-        .and_then(|v| v.get("id"))
+/// Decode a select response. supabasic hands back error bodies as if they
+/// were results, so anything but an array is classified as an error.
+fn decode_rows(response: Value) -> Result<Vec<EventRowDb>> {
+    match response {
+        Value::Array(rows) => rows.into_iter().map(decode_row).collect(),
+        other => Err(from_postgrest(&other)),
+    }
+}
+
+fn row_id(row: &Value, column: &str) -> Result<Uuid> {
+    row.get(column)
         .and_then(|v| v.as_str())
         .ok_or_else(|| ChronovoxError::MissingField("id".into()))?
         .parse::<Uuid>()
-        .map_err(|_| ChronovoxError::MissingField("id parse".into()))?;
-
-    Ok(event_id)
-}
-
-#[derive(Debug, serde::Deserialize)]
-struct EventRowDb {
-    id: Uuid,
-    frame_id: i64,
-    r_um: i64,
-    lat_code: i64,
-    lon_code: i64,
-    ticks: i64,
-    kind: String,
-    move_offset: Option<serde_json::Value>,
-    payload: Option<serde_json::Value>,
-}
-
-impl EventRowDb {
-    fn into_event(self) -> ChronoEvent {
-        ChronoEvent {
-            id: UvoxId {
-                frame_id: self.frame_id as u64,
-                r_um: self.r_um as u64,
-                lat_code: self.lat_code, // ✅ keep as i64
-                lon_code: self.lon_code, // ✅ keep as i64
-            },
-            t: TimeDelta::from_ticks(self.ticks, "nanoseconds"),
-            kind: match self.kind.as_str() {
-                "Spawn" => EventKind::Spawn,
-                "Despawn" => EventKind::Despawn,
-                other => EventKind::Custom(other.to_string()),
-            },
-            payload: self.payload.or(self.move_offset),
-        }
-    }
+        .map_err(|err| ChronovoxError::Decode { row_id: None, reason: format!("bad {column}: {err}") })
 }
 
-
 pub async fn fetch_events_for_entity(
     supa: &Supabase,
     entity_id: Uuid,
 ) -> Result<Timeline> {
-    let rows: Vec<EventRowDb> = supa
-        .from("events")
-        .select("id, frame_id, r_um, lat_code, lon_code, ticks, kind, move_offset, payload")
-        .eq("entity_id", &entity_id.to_string())
-        .execute_typed()
-        .await?;
+    fetch_events_for_entity_with(supa, entity_id, &PayloadSchemas::new()).await
+}
+
+/// Like [`fetch_events_for_entity`], failing on the first row whose payload
+/// violates `schemas`. Events come back in chronological order.
+pub async fn fetch_events_for_entity_with(
+    supa: &Supabase,
+    entity_id: Uuid,
+    schemas: &PayloadSchemas,
+) -> Result<Timeline> {
+    EventTable::default().fetch_events_for_entity(supa, entity_id, schemas).await
+}
+
+/// Entity ids per request in [`fetch_events_for_entities`], keeping URLs short.
+pub const FETCH_BATCH_SIZE: usize = 100;
+
+pub async fn fetch_events_for_entities(
+    supa: &Supabase,
+    entity_ids: &[Uuid],
+) -> Result<HashMap<Uuid, Timeline>> {
+    fetch_events_for_entities_with(supa, entity_ids, &PayloadSchemas::new()).await
+}
 
+/// Fetch the histories of many entities, [`FETCH_BATCH_SIZE`] per request,
+/// paging through each batch. Every requested id gets a timeline, empty if
+/// it has no events.
+pub async fn fetch_events_for_entities_with(
+    supa: &Supabase,
+    entity_ids: &[Uuid],
+    schemas: &PayloadSchemas,
+) -> Result<HashMap<Uuid, Timeline>> {
+    EventTable::default().fetch_events_for_entities(supa, entity_ids, schemas).await
+}
+
+/// PostgREST parameters for everything in `query` but the entity filter.
+fn query_params(query: &EventQuery, table: &EventTable) -> String {
+    let (ticks_col, id_col) = (table.column("ticks"), table.column("id"));
+    let mut params = format!("{}&limit={}", order(table), query.limit);
+    if let Some(start) = query.start_ns {
+        params.push_str(&format!("&{ticks_col}=gte.{start}"));
+    }
+    if let Some(end) = query.end_ns {
+        params.push_str(&format!("&{ticks_col}=lte.{end}"));
+    }
+    if let Some(PageCursor { ticks, id }) = query.after {
+        params.push_str(&format!(
+            "&or=({ticks_col}.gt.{ticks},and({ticks_col}.eq.{ticks},{id_col}.gt.{id}))"
+        ));
+    }
+    params
+}
+
+/// Chronological, with the row id breaking ties so pages are stable.
+fn order(table: &EventTable) -> String {
+    format!("order={}.asc,{}.asc", table.column("ticks"), table.column("id"))
+}
+
+pub async fn fetch_events_page(
+    supa: &Supabase,
+    entity_id: Uuid,
+    query: &EventQuery,
+) -> Result<EventPage> {
+    fetch_events_page_with(supa, entity_id, query, &PayloadSchemas::new()).await
+}
+
+/// Fetch one chronologically ordered page of an entity's history. The
+/// server may return fewer than `query.limit` rows (PostgREST's `max-rows`),
+/// so `next` is set for every non-empty page.
+pub async fn fetch_events_page_with(
+    supa: &Supabase,
+    entity_id: Uuid,
+    query: &EventQuery,
+    schemas: &PayloadSchemas,
+) -> Result<EventPage> {
+    EventTable::default().fetch_events_page(supa, entity_id, query, schemas).await
+}
+
+/// All of an entity's events with ticks in `[start_ns, end_ns]`, fetched
+/// page by page.
+pub async fn fetch_events_in_range(
+    supa: &Supabase,
+    entity_id: Uuid,
+    start_ns: i64,
+    end_ns: i64,
+) -> Result<Timeline> {
+    let mut query = EventQuery::new().with_range(start_ns, end_ns);
     let mut timeline = Timeline::new();
-    for row in rows {
-        timeline.push(row.into_event());
+    loop {
+        let page = fetch_events_page(supa, entity_id, &query).await?;
+        timeline.events.extend(page.timeline.events);
+        match page.next {
+            Some(cursor) => query = query.with_after(cursor),
+            None => return Ok(timeline),
+        }
     }
+}
+
+pub fn stream_events_for_entity(
+    supa: &Supabase,
+    entity_id: Uuid,
+    query: EventQuery,
+) -> impl Stream<Item = Result<ChronoEvent>> + '_ {
+    stream_events_for_entity_with(supa, entity_id, query, PayloadSchemas::new())
+}
+
+/// Stream an entity's history in chronological order, fetching one page of
+/// `query.limit` events at a time as the stream is polled.
+pub fn stream_events_for_entity_with(
+    supa: &Supabase,
+    entity_id: Uuid,
+    query: EventQuery,
+    schemas: PayloadSchemas,
+) -> impl Stream<Item = Result<ChronoEvent>> + '_ {
+    EventTable::default().stream_events(supa, entity_id, query, schemas)
+}
+
+/// The same operations against a mapped table.
+impl EventTable {
+    pub async fn insert_events<'a>(
+        &self,
+        supa: &Supabase,
+        entity_id: Uuid,
+        events: impl IntoIterator<Item = &'a ChronoEvent>,
+    ) -> Result<Vec<Uuid>> {
+        let rows = events
+            .into_iter()
+            .map(|e| event_row(entity_id, e))
+            .collect::<Result<Vec<Value>>>()?;
+
+        let mut ids = Vec::with_capacity(rows.len());
+        for chunk in rows.chunks(INSERT_BATCH_SIZE) {
+            if let Some(inserted) = post_rows(supa, self, chunk).await? {
+                ids.extend(inserted);
+                continue;
+            }
+            // The batch was rejected as a whole: look up which rows are
+            // stored and send the rest, again if a concurrent writer got in
+            // first. A rejection that stores nothing new isn't about ids.
+            let chunk_ids = chunk.iter().map(|row| row_id(row, "id")).collect::<Result<Vec<_>>>()?;
+            let mut known = None;
+            loop {
+                let stored = self.stored_ids(supa, &chunk_ids).await?;
+                if known == Some(stored.len()) {
+                    return Err(ChronovoxError::Conflict(format!(
+                        "unique violation in {} on rows not yet stored",
+                        self.name
+                    )));
+                }
+                known = Some(stored.len());
+                let missing: Vec<Value> = chunk
+                    .iter()
+                    .zip(&chunk_ids)
+                    .filter(|(_, id)| !stored.contains(id))
+                    .map(|(row, _)| row.clone())
+                    .collect();
+                if missing.is_empty() || post_rows(supa, self, &missing).await?.is_some() {
+                    break;
+                }
+            }
+            ids.extend(chunk_ids);
+        }
+
+        Ok(ids)
+    }
+
+    /// Which of `ids` already have a row, [`FETCH_BATCH_SIZE`] per request.
+    async fn stored_ids(&self, supa: &Supabase, ids: &[Uuid]) -> Result<HashSet<Uuid>> {
+        let id = self.column("id");
+        let mut stored = HashSet::new();
+        for chunk in ids.chunks(FETCH_BATCH_SIZE) {
+            let list: Vec<String> = chunk.iter().map(Uuid::to_string).collect();
+            let response = supa
+                .from(&self.name)
+                .select(&format!("{id}&{id}=in.({})", list.join(",")))
+                .execute()
+                .await?;
+            match response {
+                Value::Array(rows) => {
+                    for row in &rows {
+                        stored.insert(row_id(row, id)?);
+                    }
+                }
+                other => return Err(from_postgrest(&other)),
+            }
+        }
+        Ok(stored)
+    }
+
+    pub async fn fetch_events_for_entity(
+        &self,
+        supa: &Supabase,
+        entity_id: Uuid,
+        schemas: &PayloadSchemas,
+    ) -> Result<Timeline> {
+        let filter = format!("{}=eq.{entity_id}", self.column("entity_id"));
+        let rows = self.fetch_all_rows(supa, ROW_COLUMNS, &filter).await?;
 
-    Ok(timeline)
+        let mut timeline = Timeline::with_schemas(schemas.clone());
+        for row in rows {
+            let event = row.into_event()?;
+            schemas.check(&event)?;
+            timeline.push(event);
+        }
+
+        Ok(timeline)
+    }
+
+    pub async fn fetch_events_for_entities(
+        &self,
+        supa: &Supabase,
+        entity_ids: &[Uuid],
+        schemas: &PayloadSchemas,
+    ) -> Result<HashMap<Uuid, Timeline>> {
+        let mut timelines: HashMap<Uuid, Timeline> = entity_ids
+            .iter()
+            .map(|id| (*id, Timeline::with_schemas(schemas.clone())))
+            .collect();
+
+        let mut columns = vec!["entity_id"];
+        columns.extend_from_slice(ROW_COLUMNS);
+        for chunk in entity_ids.chunks(FETCH_BATCH_SIZE) {
+            let ids: Vec<String> = chunk.iter().map(Uuid::to_string).collect();
+            let filter = format!("{}=in.({})", self.column("entity_id"), ids.join(","));
+            let rows = self.fetch_all_rows(supa, &columns, &filter).await?;
+
+            for row in rows {
+                let entity_id = row.entity_id.ok_or_else(|| ChronovoxError::MissingField("entity_id".into()))?;
+                let event = row.into_event()?;
+                schemas.check(&event)?;
+                timelines
+                    .entry(entity_id)
+                    .or_insert_with(|| Timeline::with_schemas(schemas.clone()))
+                    .push(event);
+            }
+        }
+
+        Ok(timelines)
+    }
+
+    pub async fn fetch_events_page(
+        &self,
+        supa: &Supabase,
+        entity_id: Uuid,
+        query: &EventQuery,
+        schemas: &PayloadSchemas,
+    ) -> Result<EventPage> {
+        let filter = format!("{}=eq.{entity_id}", self.column("entity_id"));
+        let rows = self.fetch_rows(supa, ROW_COLUMNS, &filter, query).await?;
+
+        // A short page doesn't mean the end: the server may cap it below
+        // `query.limit`. Only an empty page does.
+        let next = rows.last().map(|row| PageCursor { ticks: row.ticks, id: row.id });
+
+        let mut timeline = Timeline::with_schemas(schemas.clone());
+        for row in rows {
+            let event = row.into_event()?;
+            schemas.check(&event)?;
+            timeline.push(event);
+        }
+
+        Ok(EventPage { timeline, next })
+    }
+
+    /// Every row matching `filter`, in cursor order. PostgREST silently
+    /// caps responses at its `max-rows`, so pages are requested until one
+    /// comes back empty.
+    async fn fetch_all_rows(&self, supa: &Supabase, columns: &[&str], filter: &str) -> Result<Vec<EventRowDb>> {
+        let mut query = EventQuery::new();
+        let mut rows = Vec::new();
+        loop {
+            let page = self.fetch_rows(supa, columns, filter, &query).await?;
+            let Some(last) = page.last() else { return Ok(rows) };
+            query = query.with_after(PageCursor { ticks: last.ticks, id: last.id });
+            rows.extend(page);
+        }
+    }
+
+    /// One page of rows matching `filter` (a PostgREST `column=op.value`).
+    async fn fetch_rows(
+        &self,
+        supa: &Supabase,
+        columns: &[&str],
+        filter: &str,
+        query: &EventQuery,
+    ) -> Result<Vec<EventRowDb>> {
+        // supabasic lacks most filters; PostgREST accepts them appended to the select
+        let response = supa
+            .from(&self.name)
+            .select(&format!("{}&{}&{filter}", self.select_list(columns), query_params(query, self)))
+            .execute()
+            .await?;
+        decode_rows(response)
+    }
+
+    pub fn stream_events<'a>(
+        &self,
+        supa: &'a Supabase,
+        entity_id: Uuid,
+        query: EventQuery,
+        schemas: PayloadSchemas,
+    ) -> impl Stream<Item = Result<ChronoEvent>> + use<'a> {
+        let table = self.clone();
+        stream::try_unfold(Some(query), move |query| {
+            let (table, schemas) = (table.clone(), schemas.clone());
+            async move {
+                let Some(query) = query else { return Ok::<_, ChronovoxError>(None) };
+                let page = table.fetch_events_page(supa, entity_id, &query, &schemas).await?;
+                let next = page.next.map(|cursor| query.with_after(cursor));
+                Ok(Some((stream::iter(page.timeline.events.into_iter().map(Ok)), next)))
+            }
+        })
+        .try_flatten()
+    }
 }
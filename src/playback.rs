@@ -0,0 +1,476 @@
+use std::collections::{HashMap, HashSet};
+use chrono::{DateTime, Utc};
+use futures::{future, Stream, StreamExt};
+use uvoxxyz::convert::UvoxIdExt;
+use uvoxxyz::types::CoordSystem;
+use uuid::Uuid;
+use crate::{ChronoEvent, EventKind, EntityState, UvoxId, Cartesian, TimeDelta, Thresholds, Timeline, Result};
+use crate::error::ChronovoxError;
+use crate::custom::{CustomEvent, CustomHandler, apply_custom};
+use crate::environment::{Environment, Region};
+
+/// Settings that shape how a `Timeline` is played back.
+#[derive(Debug, Clone, Default)]
+pub struct PlaybackConfig {
+    /// Environment used when no region or frame default matches.
+    pub default_env: Environment,
+    /// Per-frame defaults (e.g. vacuum for space frames).
+    pub frames: HashMap<u64, Environment>,
+    /// Region defaults, checked in order before frame defaults; first match wins.
+    pub regions: Vec<(Region, Environment)>,
+    /// Relax entity temperatures toward ambient between events (Newtonian cooling).
+    /// Only entities with a `thermal_tau_s` are affected.
+    pub thermal_relaxation: bool,
+    /// Thresholds per material name, for entities spawned with a `material`.
+    pub materials: HashMap<String, Thresholds>,
+    /// Playback hooks for `Custom` events, by name.
+    pub custom: HashMap<String, CustomHandler>,
+    /// Knowledge time: ignore events recorded after it.
+    pub as_of: Option<DateTime<Utc>>,
+}
+
+impl PlaybackConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_default_env(mut self, env: Environment) -> Self {
+        self.default_env = env;
+        self
+    }
+
+    pub fn with_frame(mut self, frame_id: u64, env: Environment) -> Self {
+        self.frames.insert(frame_id, env);
+        self
+    }
+
+    pub fn with_region(mut self, region: Region, env: Environment) -> Self {
+        self.regions.push((region, env));
+        self
+    }
+
+    pub fn with_thermal_relaxation(mut self, enabled: bool) -> Self {
+        self.thermal_relaxation = enabled;
+        self
+    }
+
+    pub fn with_material(mut self, material: impl Into<String>, thresholds: Thresholds) -> Self {
+        self.materials.insert(material.into(), thresholds);
+        self
+    }
+
+    /// Handle `Custom(E::NAME)` events with [`CustomEvent::apply`].
+    pub fn with_custom<E: CustomEvent>(mut self) -> Self {
+        self.custom.insert(E::NAME.to_string(), apply_custom::<E>);
+        self
+    }
+
+    pub fn with_as_of(mut self, knowledge: DateTime<Utc>) -> Self {
+        self.as_of = Some(knowledge);
+        self
+    }
+
+    /// Whether `e` had been recorded by the knowledge time, if one is set.
+    pub fn knows(&self, e: &ChronoEvent) -> bool {
+        self.as_of.is_none_or(|knowledge| e.recorded_at <= knowledge)
+    }
+
+    /// Default environment at a position (meters) in `frame_id`.
+    pub fn environment_at(&self, frame_id: u64, pos: &Cartesian) -> Environment {
+        self.regions
+            .iter()
+            .find(|(region, _)| region.contains(frame_id, pos))
+            .map(|(_, env)| *env)
+            .or_else(|| self.frames.get(&frame_id).copied())
+            .unwrap_or(self.default_env)
+    }
+}
+
+/// A region-scoped `Ambient` event still in effect.
+#[derive(Debug, Clone)]
+struct AmbientField {
+    region: Region,
+    temperature: Option<f64>,
+    pressure: Option<f64>,
+    until_ns: Option<i64>,
+}
+
+/// Incremental playback: feed events in chronological order and read the
+/// reconstructed state at any point.
+#[derive(Debug, Clone)]
+pub struct Playback {
+    config: PlaybackConfig,
+    state: HashMap<UvoxId, EntityState>,
+    ambient: Vec<AmbientField>,
+    /// Time the state has been brought forward to.
+    now_ns: Option<i64>,
+    /// Derived `ConditionChange` events not yet drained.
+    transitions: Vec<ChronoEvent>,
+}
+
+impl Playback {
+    pub fn new(config: PlaybackConfig) -> Self {
+        Self {
+            config,
+            state: HashMap::new(),
+            ambient: Vec::new(),
+            now_ns: None,
+            transitions: Vec::new(),
+        }
+    }
+
+    pub fn state(&self) -> &HashMap<UvoxId, EntityState> {
+        &self.state
+    }
+
+    pub(crate) fn state_mut(&mut self) -> &mut HashMap<UvoxId, EntityState> {
+        &mut self.state
+    }
+
+    pub fn into_state(self) -> HashMap<UvoxId, EntityState> {
+        self.state
+    }
+
+    /// Take the `ConditionChange` events derived so far, in time order and
+    /// by entity id within a time. Each is stamped with the playback time
+    /// at which the crossing was observed: the event (or `advance_to`) that
+    /// revealed it. A crossing reached by thermal relaxation between two
+    /// events is therefore stamped with the later event's time, not the
+    /// moment the temperature actually crossed.
+    pub fn drain_transitions(&mut self) -> Vec<ChronoEvent> {
+        std::mem::take(&mut self.transitions)
+    }
+
+    /// Bring the state forward to `t_ns` without applying an event:
+    /// relaxes temperatures (if enabled), expires ambient fields that ended
+    /// and re-applies the active ones.
+    pub fn advance_to(&mut self, t_ns: i64) {
+        let from = self.now_ns.unwrap_or(t_ns);
+        if self.config.thermal_relaxation && t_ns > from {
+            // Relax piecewise so fields ending mid-interval stop counting as ambient
+            let mut bounds: Vec<i64> = self
+                .ambient
+                .iter()
+                .filter_map(|f| f.until_ns)
+                .filter(|until| *until > from && *until < t_ns)
+                .collect();
+            bounds.sort_unstable();
+            bounds.push(t_ns);
+
+            let mut start = from;
+            for end in bounds {
+                self.relax((end - start) as f64 * 1e-9);
+                self.ambient.retain(|f| f.until_ns.is_none_or(|until| until > end));
+                start = end;
+            }
+        }
+
+        self.ambient.retain(|f| f.until_ns.is_none_or(|until| until > t_ns));
+        self.now_ns = Some(from.max(t_ns));
+        self.apply_ambient();
+        self.update_conditions();
+    }
+
+    /// Apply the next event in chronological order.
+    /// Events recorded after the config's knowledge time are ignored.
+    pub fn apply(&mut self, e: &ChronoEvent) {
+        if !self.config.knows(e) {
+            return;
+        }
+        self.advance_to(e.t.ticks("nanoseconds"));
+
+        let state = &mut self.state;
+        match &e.kind {
+            // === Core Lifecycle ===
+            EventKind::Spawn => {
+                let init = e.spawn_state().unwrap_or_default();
+                let pos = init.pos.unwrap_or_else(|| e.id.to_cartesian(CoordSystem::Math));
+                let env = self.config.environment_at(e.id.frame_id, &pos);
+                let temperature = init.temperature.unwrap_or(env.temperature);
+                let pressure = init.pressure.unwrap_or(env.pressure);
+                let thresholds = init
+                    .thresholds
+                    .or_else(|| {
+                        let material = init.material.as_ref()?;
+                        self.config.materials.get(material).copied()
+                    })
+                    .unwrap_or_default();
+                state.insert(
+                    e.id,
+                    EntityState {
+                        pos,
+                        alive: true,
+                        temperature,
+                        pressure,
+                        thermal_tau_s: init.thermal_tau_s,
+                        material: init.material,
+                        thresholds,
+                        condition: thresholds.evaluate(temperature, pressure),
+                        fields: init.fields,
+                    },
+                );
+            }
+            EventKind::Despawn => {
+                if let Some(s) = state.get_mut(&e.id) {
+                    s.alive = false;
+                }
+            }
+
+            // === Movement ===
+            EventKind::Move { offset } => {
+                if let Some(s) = state.get_mut(&e.id) {
+                    s.pos.x += offset.x;
+                    s.pos.y += offset.y;
+                    s.pos.z += offset.z;
+                }
+            }
+            EventKind::Teleport { new_pos } => {
+                if let Some(s) = state.get_mut(&e.id) {
+                    s.pos = *new_pos;
+                }
+            }
+
+            // === Environment ===
+            EventKind::TemperatureChange { delta_c } => {
+                if let Some(s) = state.get_mut(&e.id) {
+                    s.temperature += delta_c;
+                }
+            }
+            EventKind::PressureChange { delta_pa } => {
+                if let Some(s) = state.get_mut(&e.id) {
+                    s.pressure += delta_pa;
+                }
+            }
+
+            EventKind::Radiation { dose: _ } => {
+                // TODO: accumulate radiation dose
+            }
+            EventKind::Shock { g: _ } => {
+                // TODO: apply shock/damage
+            }
+            EventKind::Ambient { region, temperature_c, pressure_pa, until } => {
+                self.ambient.push(AmbientField {
+                    region: region.clone(),
+                    temperature: *temperature_c,
+                    pressure: *pressure_pa,
+                    until_ns: until.as_ref().map(|u| u.ticks("nanoseconds")),
+                });
+            }
+
+            // === Material / Integrity ===
+            EventKind::Degrade { rate: _ } => {
+                // TODO: mark progressive degradation
+            }
+            EventKind::Leak { severity: _ } => {
+                // TODO: track fluid/gas loss
+            }
+            EventKind::Fracture { plane: _ } => {
+                // TODO: mark fracture in state
+            }
+
+            // === Interactions ===
+            EventKind::Bond { with: _ } => {
+                // TODO: link entities
+            }
+            EventKind::Unbond { from: _ } => {
+                // TODO: unlink entities
+            }
+            EventKind::Transfer { to: _, what: _, amount: _ } => {
+                // TODO: handle resource transfer
+            }
+
+            // === Audit ===
+            EventKind::Retract { .. } | EventKind::Correct { .. } => {
+                // resolved before playback (see `Timeline::effective_events`)
+            }
+
+            // === Derived ===
+            EventKind::ConditionChange { .. } => {
+                // derived by playback itself; nothing to apply
+            }
+
+            // === Wild Card ===
+            EventKind::Custom(name) => {
+                if let (Some(handler), Some(s)) = (self.config.custom.get(name), state.get_mut(&e.id)) {
+                    handler(e, s);
+                }
+            }
+        }
+
+        self.apply_ambient();
+        self.update_conditions();
+    }
+
+    /// Apply a stream of events in chronological order, yielding each
+    /// event's entity state as it's applied (events for entities not yet
+    /// spawned yield nothing). The first error ends the stream.
+    ///
+    /// Only the current state is kept, so a retraction or correction can't
+    /// be honoured: it ends the stream with
+    /// [`ChronovoxError::UnresolvedAmendment`]. Streams that may carry
+    /// amendments need [`follow_with_history`](Self::follow_with_history).
+    pub fn follow<'a, S>(&'a mut self, events: S) -> impl Stream<Item = Result<(UvoxId, EntityState)>> + 'a
+    where
+        S: Stream<Item = Result<ChronoEvent>> + 'a,
+    {
+        self.follow_inner(events, false)
+    }
+
+    /// Like [`follow`](Self::follow), but every event seen is kept so
+    /// amendments can be honoured: a retraction or correction of an event
+    /// already applied rebuilds the state from that history, ending where
+    /// `Timeline::playback` would.
+    ///
+    /// Memory grows with the stream (a copy of each event, plus its id), and
+    /// each rebuild replays everything seen so far. Suited to replays of a
+    /// bounded history rather than long-lived subscriptions.
+    pub fn follow_with_history<'a, S>(
+        &'a mut self,
+        events: S,
+    ) -> impl Stream<Item = Result<(UvoxId, EntityState)>> + 'a
+    where
+        S: Stream<Item = Result<ChronoEvent>> + 'a,
+    {
+        self.follow_inner(events, true)
+    }
+
+    fn follow_inner<'a, S>(
+        &'a mut self,
+        events: S,
+        keep_history: bool,
+    ) -> impl Stream<Item = Result<(UvoxId, EntityState)>> + 'a
+    where
+        S: Stream<Item = Result<ChronoEvent>> + 'a,
+    {
+        let mut failed = false;
+        let mut history = Timeline::new();
+        let mut seen: HashSet<Uuid> = HashSet::new();
+        let mut amended: HashSet<Uuid> = HashSet::new();
+        events
+            .map(move |e| match e {
+                Ok(e) if !keep_history && e.amends().is_some() => {
+                    Err(ChronovoxError::UnresolvedAmendment(e.event_id))
+                }
+                other => other,
+            })
+            .take_while(move |e| {
+                let go = !failed;
+                failed |= e.is_err();
+                future::ready(go)
+            })
+            .filter_map(move |e| {
+                let update = match e {
+                    Ok(e) => {
+                        let mut rebuild = false;
+                        if keep_history {
+                            // Amending something already applied, or arriving after an amendment of it
+                            rebuild = e.amends().is_some_and(|target| seen.contains(&target))
+                                || amended.contains(&e.event_id);
+                            seen.insert(e.event_id);
+                            amended.extend(e.amends());
+                            history.push(e.clone());
+                        }
+                        if rebuild {
+                            self.rebuild(&history);
+                        } else {
+                            self.apply(&e);
+                        }
+                        self.state.get(&e.id).cloned().map(|s| Ok((e.id, s)))
+                    }
+                    Err(err) => Some(Err(err)),
+                };
+                future::ready(update)
+            })
+    }
+
+    /// Replace the state with a fresh playback of `history`'s effective
+    /// events. Transitions already recorded are kept.
+    fn rebuild(&mut self, history: &Timeline) {
+        let mut fresh = Playback::new(self.config.clone());
+        for e in &history.effective_events(&self.config) {
+            fresh.apply(e);
+        }
+        self.state = fresh.state;
+        self.ambient = fresh.ambient;
+        self.now_ns = fresh.now_ns;
+    }
+
+    /// Re-derive each live entity's condition, recording a transition event
+    /// for every change. Entities are visited in id order so simultaneous
+    /// transitions come out the same on every run.
+    fn update_conditions(&mut self) {
+        let t = TimeDelta::from_ticks(self.now_ns.unwrap_or_default(), "nanoseconds");
+        let mut ids: Vec<UvoxId> = self.state.keys().copied().collect();
+        ids.sort_unstable_by_key(UvoxId::as_tuple);
+        for id in ids {
+            let Some(s) = self.state.get_mut(&id).filter(|s| s.alive) else {
+                continue;
+            };
+            let next = s.thresholds.evaluate(s.temperature, s.pressure);
+            if next != s.condition {
+                self.transitions.push(ChronoEvent::new(
+                    id,
+                    t.clone(),
+                    EventKind::ConditionChange { from: s.condition, to: next },
+                ));
+                s.condition = next;
+            }
+        }
+    }
+
+    /// Newtonian cooling of every live entity with a time constant over `dt_s` seconds.
+    fn relax(&mut self, dt_s: f64) {
+        for (id, s) in self.state.iter_mut() {
+            let Some(tau) = s.thermal_tau_s.filter(|tau| *tau > 0.0) else {
+                continue;
+            };
+            if !s.alive {
+                continue;
+            }
+            let ambient = ambient_at(&self.config, &self.ambient, id.frame_id, &s.pos);
+            s.temperature = ambient.temperature + (s.temperature - ambient.temperature) * (-dt_s / tau).exp();
+        }
+    }
+
+    /// Impose active ambient fields on every live entity inside them.
+    /// Later fields win where regions overlap. Entities that relax thermally
+    /// keep their temperature; the field only sets what they relax toward.
+    fn apply_ambient(&mut self) {
+        let relaxing = self.config.thermal_relaxation;
+        for field in &self.ambient {
+            for (id, s) in self.state.iter_mut() {
+                if !s.alive || !field.region.contains(id.frame_id, &s.pos) {
+                    continue;
+                }
+                if let Some(t) = field.temperature
+                    && !(relaxing && s.thermal_tau_s.is_some())
+                {
+                    s.temperature = t;
+                }
+                if let Some(p) = field.pressure {
+                    s.pressure = p;
+                }
+            }
+        }
+    }
+}
+
+/// Ambient conditions at a position: the config default, overridden by any
+/// active fields containing it.
+fn ambient_at(
+    config: &PlaybackConfig,
+    fields: &[AmbientField],
+    frame_id: u64,
+    pos: &Cartesian,
+) -> Environment {
+    let mut env = config.environment_at(frame_id, pos);
+    for field in fields.iter().filter(|f| f.region.contains(frame_id, pos)) {
+        if let Some(t) = field.temperature {
+            env.temperature = t;
+        }
+        if let Some(p) = field.pressure {
+            env.pressure = p;
+        }
+    }
+    env
+}
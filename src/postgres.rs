@@ -0,0 +1,195 @@
+use futures::channel::mpsc;
+use futures::{stream, Stream, StreamExt, TryStreamExt};
+use serde_json::Value;
+use tokio_postgres::{AsyncMessage, Client, NoTls};
+use uuid::Uuid;
+use crate::row::{decode_row, event_row, WRITTEN_COLUMNS};
+use crate::table::quote_ident;
+use crate::{
+    ChronoEvent, ChronovoxError, EventPage, EventQuery, EventTable, PageCursor, PayloadSchemas, Result, Timeline,
+};
+
+/// Event store talking to Postgres directly, with the same row layout as
+/// the Supabase backend. Connections are driven on spawned Tokio tasks.
+pub struct PgEventStore {
+    client: Client,
+    url: String,
+    table: EventTable,
+}
+
+impl PgEventStore {
+    /// Connect (without TLS) to the default `events` table.
+    pub async fn connect(url: &str) -> Result<Self> {
+        Self::connect_with(url, EventTable::default()).await
+    }
+
+    pub async fn connect_with(url: &str, table: EventTable) -> Result<Self> {
+        let (client, connection) = tokio_postgres::connect(url, NoTls).await?;
+        tokio::spawn(async move {
+            let _ = connection.await;
+        });
+        Ok(Self { client, url: url.to_string(), table })
+    }
+
+    pub fn table(&self) -> &EventTable {
+        &self.table
+    }
+
+    /// Create the table and its indexes if missing.
+    pub async fn migrate(&self) -> Result<()> {
+        self.client.batch_execute(&self.table.migration_sql()).await?;
+        Ok(())
+    }
+
+    /// `NOTIFY` channel announcing inserts; the payload is the entity id.
+    fn channel(&self) -> String {
+        format!("chronovox_{}", self.table.name)
+    }
+
+    /// Append events in a single transaction: either all new rows are
+    /// stored or none are. Events already stored are skipped. Returns the
+    /// ids in input order.
+    pub async fn insert_events<'a>(
+        &mut self,
+        entity_id: Uuid,
+        events: impl IntoIterator<Item = &'a ChronoEvent>,
+    ) -> Result<Vec<Uuid>> {
+        let mut ids = Vec::new();
+        let mut rows = Vec::new();
+        for e in events {
+            ids.push(e.event_id);
+            rows.push(self.table.store_row(event_row(entity_id, e)?));
+        }
+
+        // Only chronovox's columns, so any others the table has keep their defaults
+        let table = quote_ident(&self.table.name);
+        let columns = WRITTEN_COLUMNS
+            .iter()
+            .map(|c| quote_ident(self.table.column(c)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let sql = format!(
+            "insert into {table} ({columns}) select {columns} from jsonb_populate_recordset(null::{table}, $1) \
+             on conflict ({}) do nothing",
+            quote_ident(self.table.column("id")),
+        );
+        let channel = self.channel();
+        let tx = self.client.transaction().await?;
+        tx.execute(&sql, &[&Value::Array(rows)]).await?;
+        tx.execute("select pg_notify($1, $2)", &[&channel, &entity_id.to_string()]).await?;
+        tx.commit().await?;
+        Ok(ids)
+    }
+
+    /// An entity's whole history, in chronological order.
+    pub async fn fetch_events_for_entity(&self, entity_id: Uuid, schemas: &PayloadSchemas) -> Result<Timeline> {
+        let page = fetch_page(&self.client, &self.table, entity_id, &EventQuery::new(), None, schemas).await?;
+        Ok(page.timeline)
+    }
+
+    pub async fn fetch_events_page(
+        &self,
+        entity_id: Uuid,
+        query: &EventQuery,
+        schemas: &PayloadSchemas,
+    ) -> Result<EventPage> {
+        fetch_page(&self.client, &self.table, entity_id, query, Some(query.limit as i64), schemas).await
+    }
+
+    /// Like [`subscribe_to_entity`](crate::subscribe_to_entity), but woken by
+    /// `NOTIFY` from [`PgEventStore::insert_events`] instead of polling.
+    /// Uses a connection of its own.
+    pub async fn subscribe(
+        &self,
+        entity_id: Uuid,
+        after: Option<PageCursor>,
+    ) -> Result<impl Stream<Item = Result<ChronoEvent>> + use<>> {
+        let (listener, mut connection) = tokio_postgres::connect(&self.url, NoTls).await?;
+        let (wake, woken) = mpsc::unbounded();
+        let wanted = entity_id.to_string();
+        tokio::spawn(async move {
+            let mut messages = stream::poll_fn(move |cx| connection.poll_message(cx));
+            while let Some(Ok(message)) = messages.next().await {
+                if let AsyncMessage::Notification(n) = message
+                    && n.payload() == wanted
+                    && wake.unbounded_send(()).is_err()
+                {
+                    break;
+                }
+            }
+        });
+        listener.batch_execute(&format!("listen {}", quote_ident(&self.channel()))).await?;
+
+        let table = self.table.clone();
+        let events = stream::try_unfold((listener, woken, after), move |(listener, mut woken, watermark)| {
+            let table = table.clone();
+            async move {
+                loop {
+                    let mut query = EventQuery::new();
+                    query.after = watermark;
+                    let limit = Some(query.limit as i64);
+                    let page = fetch_page(&listener, &table, entity_id, &query, limit, &PayloadSchemas::new()).await?;
+                    if let Some(last) = page.timeline.events.last() {
+                        let watermark = Some(PageCursor { ticks: last.t.ticks("nanoseconds"), id: last.event_id });
+                        let events = stream::iter(page.timeline.events.into_iter().map(Ok));
+                        return Ok::<_, ChronovoxError>(Some((events, (listener, woken, watermark))));
+                    }
+                    if woken.next().await.is_none() {
+                        return Ok(None); // connection closed
+                    }
+                }
+            }
+        })
+        .try_flatten();
+        Ok(events)
+    }
+}
+
+/// One page in `(ticks, id)` order; `limit: None` reads to the end.
+async fn fetch_page(
+    client: &Client,
+    table: &EventTable,
+    entity_id: Uuid,
+    query: &EventQuery,
+    limit: Option<i64>,
+    schemas: &PayloadSchemas,
+) -> Result<EventPage> {
+    let (entity, ticks, id) = (
+        quote_ident(table.column("entity_id")),
+        quote_ident(table.column("ticks")),
+        quote_ident(table.column("id")),
+    );
+    let sql = format!(
+        "select to_jsonb(r) from {} r where {entity} = $1 \
+         and ($2::bigint is null or {ticks} >= $2) \
+         and ($3::bigint is null or {ticks} <= $3) \
+         and ($4::bigint is null or ({ticks}, {id}) > ($4, $5::uuid)) \
+         order by {ticks}, {id} limit $6",
+        quote_ident(&table.name),
+    );
+    let (after_ticks, after_id) = query.after.map(|c| (c.ticks, c.id)).unzip();
+    let rows = client
+        .query(&sql, &[&entity_id, &query.start_ns, &query.end_ns, &after_ticks, &after_id, &limit])
+        .await?;
+
+    let mut decoded = Vec::with_capacity(rows.len());
+    for row in &rows {
+        let stored: Value = row.get(0);
+        decoded.push(decode_row(table.load_row(stored))?);
+    }
+
+    let next = match limit {
+        Some(limit) if decoded.len() as i64 >= limit => {
+            decoded.last().map(|row| PageCursor { ticks: row.ticks, id: row.id })
+        }
+        _ => None,
+    };
+
+    let mut timeline = Timeline::with_schemas(schemas.clone());
+    for row in decoded {
+        let event = row.into_event()?;
+        schemas.check(&event)?;
+        timeline.push(event);
+    }
+    Ok(EventPage { timeline, next })
+}
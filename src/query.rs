@@ -0,0 +1,59 @@
+use uuid::Uuid;
+use crate::Timeline;
+
+/// Default [`EventQuery::limit`].
+pub const PAGE_SIZE: usize = 1000;
+
+/// Position after the last event of a page: its ticks and row id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageCursor {
+    pub ticks: i64,
+    pub id: Uuid,
+}
+
+/// A time-bounded page of an entity's history, evaluated by the server.
+#[derive(Debug, Clone)]
+pub struct EventQuery {
+    /// Inclusive lower bound on ticks (ns).
+    pub start_ns: Option<i64>,
+    /// Inclusive upper bound on ticks (ns).
+    pub end_ns: Option<i64>,
+    pub limit: usize,
+    /// Resume after this position (keyset pagination).
+    pub after: Option<PageCursor>,
+}
+
+impl Default for EventQuery {
+    fn default() -> Self {
+        Self { start_ns: None, end_ns: None, limit: PAGE_SIZE, after: None }
+    }
+}
+
+impl EventQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_range(mut self, start_ns: i64, end_ns: i64) -> Self {
+        self.start_ns = Some(start_ns);
+        self.end_ns = Some(end_ns);
+        self
+    }
+
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    pub fn with_after(mut self, cursor: PageCursor) -> Self {
+        self.after = Some(cursor);
+        self
+    }
+}
+
+/// One page of results; `next` is set while more rows may follow.
+#[derive(Debug, Clone)]
+pub struct EventPage {
+    pub timeline: Timeline,
+    pub next: Option<PageCursor>,
+}
@@ -0,0 +1,125 @@
+// Row encoding shared by the persistence backends.
+
+use serde_json::{json, Value};
+use tdt::core::TimeDelta;
+use uuid::Uuid;
+use uvoxid::UvoxId;
+use crate::error::ChronovoxError;
+use crate::{ChronoEvent, EventKind, Result};
+
+/// The columns [`event_row`] fills, under chronovox's names.
+#[cfg_attr(not(feature = "postgres"), allow(dead_code))]
+pub(crate) const WRITTEN_COLUMNS: &[&str] = &[
+    "id", "entity_id", "frame_id", "r_um", "lat_code", "lon_code", "version", "causation_id", "correlation_id",
+    "ticks", "source", "author", "kind", "kind_data", "payload",
+];
+
+/// A row as written: chronovox's column names, before any [`EventTable`](crate::EventTable) renames.
+/// `timestamp` is left to the table's `default now()`, so a stored event's
+/// `recorded_at` is when the database learned of it, not the client's clock.
+pub(crate) fn event_row(entity_id: Uuid, event: &ChronoEvent) -> Result<Value> {
+    let (kind, kind_data) = encode_kind(&event.kind)?;
+    Ok(json!({
+        "id": event.event_id,
+        "entity_id": entity_id,
+        "frame_id": event.id.frame_id as i64,
+        "r_um": event.id.r_um as i64,
+        "lat_code": event.id.lat_code,
+        "lon_code": event.id.lon_code,
+        "version": event.version,
+        "causation_id": event.causation_id,
+        "correlation_id": event.correlation_id,
+        "ticks": event.t.ticks("nanoseconds"),
+        "source": event.source,
+        "author": event.author,
+        "kind": kind,
+        "kind_data": kind_data,
+        "payload": event.payload,
+    }))
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub(crate) struct EventRowDb {
+    pub(crate) id: Uuid,
+    /// Only selected by bulk fetches.
+    #[serde(default)]
+    #[cfg_attr(not(feature = "supabase"), allow(dead_code))]
+    pub(crate) entity_id: Option<Uuid>,
+    frame_id: i64,
+    r_um: i64,
+    lat_code: i64,
+    lon_code: i64,
+    version: Option<i64>,
+    causation_id: Option<Uuid>,
+    correlation_id: Option<Uuid>,
+    pub(crate) ticks: i64,
+    timestamp: Option<chrono::DateTime<chrono::Utc>>,
+    source: Option<String>,
+    author: Option<String>,
+    kind: String,
+    kind_data: Option<serde_json::Value>,
+    move_offset: Option<serde_json::Value>,
+    payload: Option<serde_json::Value>,
+}
+
+/// Parse a stored row (with chronovox's column names), reporting which row
+/// failed when it can be told.
+pub(crate) fn decode_row(row: Value) -> Result<EventRowDb> {
+    let row_id = row.get("id").and_then(Value::as_str).and_then(|id| id.parse().ok());
+    serde_json::from_value(row).map_err(|err| ChronovoxError::Decode { row_id, reason: err.to_string() })
+}
+
+impl EventRowDb {
+    /// Rebuild the event, upcasting rows written by older versions.
+    pub(crate) fn into_event(self) -> Result<ChronoEvent> {
+        let id = UvoxId {
+            frame_id: self.frame_id as u64,
+            r_um: self.r_um as u64,
+            lat_code: self.lat_code, // ✅ keep as i64
+            lon_code: self.lon_code, // ✅ keep as i64
+        };
+        let value = json!({
+            "version": self.version.unwrap_or(0),
+            "event_id": self.id,
+            "causation_id": self.causation_id,
+            "correlation_id": self.correlation_id,
+            "id": id,
+            "t": TimeDelta::from_ticks(self.ticks, "nanoseconds"),
+            "recorded_at": self.timestamp,
+            "source": self.source,
+            "author": self.author,
+            "kind": raw_kind(self.kind, self.kind_data),
+            "payload": self.payload.or(self.move_offset),
+        });
+        let row_id = self.id;
+        ChronoEvent::try_from(value).map_err(|err| match err {
+            ChronovoxError::Json(err) => ChronovoxError::Decode { row_id: Some(row_id), reason: err.to_string() },
+            ChronovoxError::Decode { row_id: None, reason } => ChronovoxError::Decode { row_id: Some(row_id), reason },
+            other => other,
+        })
+    }
+}
+
+/// Split an `EventKind` into its variant name (`kind` column) and the
+/// variant's fields (`kind_data` column, null for unit variants).
+fn encode_kind(kind: &EventKind) -> Result<(String, Option<Value>)> {
+    match serde_json::to_value(kind)? {
+        Value::String(name) => Ok((name, None)),
+        Value::Object(map) if map.len() == 1 => {
+            let (name, data) = map.into_iter().next().unwrap_or_default();
+            Ok((name, Some(data)))
+        }
+        other => Err(ChronovoxError::Db(format!("unexpected kind encoding: {other}"))),
+    }
+}
+
+/// Inverse of [`encode_kind`]: the kind JSON as stored, for the upcaster
+/// and then `ChronoEvent` to read. Without `kind_data` the kind text is
+/// passed on as is: a unit variant, or on unversioned rows a legacy `Debug`
+/// string the upcaster parses.
+fn raw_kind(kind: String, data: Option<Value>) -> Value {
+    match data {
+        Some(data) if !data.is_null() => json!({ kind: data }),
+        _ => Value::String(kind),
+    }
+}
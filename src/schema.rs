@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use crate::{ChronoEvent, CustomEvent, Result};
+use crate::error::ChronovoxError;
+
+/// Checks a payload, returning one message per violation (empty if valid).
+pub type PayloadValidator = Arc<dyn Fn(&Value) -> Vec<String> + Send + Sync>;
+
+struct KindSchema {
+    required: bool,
+    validators: Vec<PayloadValidator>,
+}
+
+/// Per-kind payload validators, keyed by [`EventKind::name`](crate::EventKind::name).
+///
+/// Kinds without an entry accept any payload.
+#[derive(Clone, Default)]
+pub struct PayloadSchemas {
+    kinds: HashMap<String, Arc<KindSchema>>,
+}
+
+impl PayloadSchemas {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Payloads of `kind`, when present, must deserialize as `T`.
+    pub fn register<T: DeserializeOwned>(self, kind: impl Into<String>) -> Self {
+        self.add(kind.into(), false, typed_validator::<T>())
+    }
+
+    /// Payloads of `kind` are mandatory and must deserialize as `T`.
+    pub fn require<T: DeserializeOwned>(self, kind: impl Into<String>) -> Self {
+        self.add(kind.into(), true, typed_validator::<T>())
+    }
+
+    /// Typed custom kinds always carry their data as the payload.
+    pub fn register_custom<E: CustomEvent>(self) -> Self {
+        self.require::<E>(E::NAME)
+    }
+
+    /// Add a hand-written validator for `kind`.
+    pub fn register_fn(
+        self,
+        kind: impl Into<String>,
+        validator: impl Fn(&Value) -> Vec<String> + Send + Sync + 'static,
+    ) -> Self {
+        self.add(kind.into(), false, Arc::new(validator))
+    }
+
+    fn add(mut self, kind: String, required: bool, validator: PayloadValidator) -> Self {
+        let (was_required, mut validators) = match self.kinds.remove(&kind) {
+            Some(schema) => (schema.required, schema.validators.clone()),
+            None => (false, Vec::new()),
+        };
+        validators.push(validator);
+        self.kinds.insert(kind, Arc::new(KindSchema { required: required || was_required, validators }));
+        self
+    }
+
+    /// All violations of `event`'s payload against its kind's schema.
+    pub fn violations(&self, event: &ChronoEvent) -> Vec<String> {
+        let Some(schema) = self.kinds.get(event.kind.name()) else {
+            return Vec::new();
+        };
+        match &event.payload {
+            None if schema.required => vec!["payload is required".to_string()],
+            None => Vec::new(),
+            Some(payload) => schema.validators.iter().flat_map(|v| v(payload)).collect(),
+        }
+    }
+
+    /// Fail with [`ChronovoxError::InvalidPayload`] if `event` has violations.
+    pub fn check(&self, event: &ChronoEvent) -> Result<()> {
+        let violations = self.violations(event);
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(ChronovoxError::InvalidPayload {
+                kind: event.kind.name().to_string(),
+                violations,
+            })
+        }
+    }
+}
+
+impl fmt::Debug for PayloadSchemas {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PayloadSchemas")
+            .field("kinds", &self.kinds.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+fn typed_validator<T: DeserializeOwned>() -> PayloadValidator {
+    Arc::new(|payload: &Value| match serde_json::from_value::<T>(payload.clone()) {
+        Ok(_) => Vec::new(),
+        Err(e) => vec![e.to_string()],
+    })
+}
@@ -0,0 +1,58 @@
+use std::time::Duration;
+use futures::{stream, Stream, TryStreamExt};
+use supabasic::Supabase;
+use uuid::Uuid;
+use crate::{ChronoEvent, ChronovoxError, EventQuery, EventTable, PageCursor, PayloadSchemas, Result};
+
+/// Follow an entity's history as it grows: yields every event after `after`
+/// (or the whole history if `None`) in chronological order, then polls every
+/// `poll_interval` for newer ones. The stream never ends on its own.
+/// [Retryable](ChronovoxError::is_retryable) failures are retried at the
+/// next poll; any other error ends it. Needs a Tokio runtime.
+///
+/// Progress is tracked with a ticks/id watermark, so events inserted later
+/// with ticks behind the watermark are not seen.
+pub fn subscribe_to_entity(
+    supa: &Supabase,
+    entity_id: Uuid,
+    after: Option<PageCursor>,
+    poll_interval: Duration,
+) -> impl Stream<Item = Result<ChronoEvent>> + '_ {
+    EventTable::default().subscribe(supa, entity_id, after, poll_interval)
+}
+
+impl EventTable {
+    /// [`subscribe_to_entity`] against this table.
+    pub fn subscribe<'a>(
+        &self,
+        supa: &'a Supabase,
+        entity_id: Uuid,
+        after: Option<PageCursor>,
+        poll_interval: Duration,
+    ) -> impl Stream<Item = Result<ChronoEvent>> + use<'a> {
+        let table = self.clone();
+        stream::try_unfold(after, move |watermark| {
+            let table = table.clone();
+            async move {
+                let mut query = EventQuery::new();
+                query.after = watermark;
+                let events = match table.fetch_events_page(supa, entity_id, &query, &PayloadSchemas::new()).await {
+                    Ok(page) => page.timeline.events,
+                    Err(err) if err.is_retryable() => Vec::new(),
+                    Err(err) => return Err(err),
+                };
+
+                let watermark = match events.last() {
+                    Some(last) => Some(PageCursor { ticks: last.t.ticks("nanoseconds"), id: last.event_id }),
+                    None => {
+                        tokio::time::sleep(poll_interval).await;
+                        watermark
+                    }
+                };
+                let events = events.into_iter().map(Ok);
+                Ok::<_, ChronovoxError>(Some((stream::iter(events), watermark)))
+            }
+        })
+        .try_flatten()
+    }
+}
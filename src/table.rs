@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+use crate::error::{ChronovoxError, Result};
+
+/// Columns chronovox reads and writes, with their Postgres types.
+/// `move_offset` is only read, for rows written before `kind_data`.
+pub const EVENT_COLUMNS: &[(&str, &str)] = &[
+    ("id", "uuid primary key"),
+    ("entity_id", "uuid not null"),
+    ("frame_id", "bigint not null"),
+    ("r_um", "bigint not null"),
+    ("lat_code", "bigint not null"),
+    ("lon_code", "bigint not null"),
+    ("version", "integer"),
+    ("causation_id", "uuid"),
+    ("correlation_id", "uuid"),
+    ("ticks", "bigint not null"),
+    ("timestamp", "timestamptz not null default now()"),
+    ("source", "text"),
+    ("author", "text"),
+    ("kind", "text not null"),
+    ("kind_data", "jsonb"),
+    ("move_offset", "jsonb"),
+    ("payload", "jsonb"),
+];
+
+/// Where events live: the table name and the actual name of any column
+/// that differs from chronovox's (see [`EVENT_COLUMNS`]).
+///
+/// The default is the `events` table created by
+/// `migrations/0001_create_events.sql`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventTable {
+    pub name: String,
+    renames: HashMap<String, String>,
+}
+
+impl Default for EventTable {
+    fn default() -> Self {
+        Self::new("events")
+    }
+}
+
+impl EventTable {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into(), renames: HashMap::new() }
+    }
+
+    /// Store chronovox's `column` as `actual`. Fails with
+    /// [`ChronovoxError::SchemaMismatch`] if `column` isn't one of
+    /// [`EVENT_COLUMNS`].
+    pub fn with_column(mut self, column: &str, actual: impl Into<String>) -> Result<Self> {
+        if !EVENT_COLUMNS.iter().any(|(c, _)| *c == column) {
+            return Err(ChronovoxError::SchemaMismatch(format!("unknown event column `{column}`")));
+        }
+        self.renames.insert(column.to_string(), actual.into());
+        Ok(self)
+    }
+
+    /// The actual name of chronovox's `column`.
+    pub fn column<'a>(&'a self, column: &'a str) -> &'a str {
+        self.renames.get(column).map_or(column, String::as_str)
+    }
+
+    /// PostgREST select list reading `columns` under chronovox's names.
+    #[cfg(feature = "supabase")]
+    pub(crate) fn select_list(&self, columns: &[&str]) -> String {
+        columns
+            .iter()
+            .map(|c| match self.renames.get(*c) {
+                Some(actual) => format!("{c}:{actual}"),
+                None => c.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    /// Rename a row's keys from chronovox's names to the actual ones.
+    #[cfg(any(feature = "supabase", feature = "postgres"))]
+    pub(crate) fn store_row(&self, row: serde_json::Value) -> serde_json::Value {
+        match row {
+            serde_json::Value::Object(map) => serde_json::Value::Object(
+                map.into_iter()
+                    .map(|(k, v)| (self.column(&k).to_string(), v))
+                    .collect::<serde_json::Map<_, _>>(),
+            ),
+            other => other,
+        }
+    }
+
+    /// Inverse of [`EventTable::store_row`].
+    #[cfg(feature = "postgres")]
+    pub(crate) fn load_row(&self, row: serde_json::Value) -> serde_json::Value {
+        match row {
+            serde_json::Value::Object(map) => serde_json::Value::Object(
+                map.into_iter()
+                    .map(|(k, v)| {
+                        let column = self.renames.iter().find(|(_, actual)| **actual == k).map(|(c, _)| c.clone());
+                        (column.unwrap_or(k), v)
+                    })
+                    .collect::<serde_json::Map<_, _>>(),
+            ),
+            other => other,
+        }
+    }
+
+    /// SQL creating the table and its indexes under this mapping.
+    pub fn migration_sql(&self) -> String {
+        let q = quote_ident;
+        let columns: Vec<String> = EVENT_COLUMNS
+            .iter()
+            .map(|(c, ty)| format!("    {} {ty}", q(self.column(c))))
+            .collect();
+        let (table, entity_id, ticks, id) =
+            (&self.name, self.column("entity_id"), self.column("ticks"), self.column("id"));
+        format!(
+            "create table if not exists {} (\n{}\n);\n\n\
+             -- per-entity history, in fetch order\n\
+             create index if not exists {} on {} ({}, {}, {});\n\
+             -- time-range queries across entities\n\
+             create index if not exists {} on {} ({});\n",
+            q(table),
+            columns.join(",\n"),
+            q(&format!("{table}_entity_id_ticks_idx")),
+            q(table),
+            q(entity_id),
+            q(ticks),
+            q(id),
+            q(&format!("{table}_ticks_idx")),
+            q(table),
+            q(ticks),
+        )
+    }
+}
+
+/// Quote a Postgres identifier.
+pub(crate) fn quote_ident(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
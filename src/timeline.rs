@@ -1,33 +1,58 @@
 use std::cmp::Ordering;
 use std::collections::HashMap;
-use crate::{ChronoEvent, EventKind, UvoxId, Cartesian};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use crate::{ChronoEvent, EventKind, UvoxId, Cartesian, PlaybackConfig, Condition, Thresholds, PayloadSchemas, Result};
+use crate::playback::Playback;
 
 #[derive(Debug, Default, Clone)]
 pub struct Timeline {
     pub events: Vec<ChronoEvent>,
+    /// Payload schemas enforced by `insert`, if any.
+    pub schemas: Option<PayloadSchemas>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct EntityState {
     pub pos: Cartesian,
     pub alive: bool,
     pub temperature: f64, // °C
     pub pressure: f64,    // Pascals
+    /// Thermal time constant (seconds); `None` disables relaxation for this entity.
+    pub thermal_tau_s: Option<f64>,
+    pub material: Option<String>,
+    /// Limits `condition` is derived from.
+    pub thresholds: Thresholds,
+    pub condition: Condition,
+    /// System-specific fields set at spawn.
+    pub fields: HashMap<String, serde_json::Value>,
 }
 
 
 impl Timeline {
     pub fn new() -> Self {
-        Self { events: Vec::new() }
+        Self { events: Vec::new(), schemas: None }
     }
 
+    /// An empty timeline whose `insert` validates payloads against `schemas`.
+    pub fn with_schemas(schemas: PayloadSchemas) -> Self {
+        Self { events: Vec::new(), schemas: Some(schemas) }
+    }
+
+    /// Append without sorting or validation.
     pub fn push(&mut self, event: ChronoEvent) {
         self.events.push(event);
     }
 
-    pub fn insert(&mut self, event: ChronoEvent) {
+    /// Insert in chronological order, rejecting payloads that violate the
+    /// timeline's schemas.
+    pub fn insert(&mut self, event: ChronoEvent) -> Result<()> {
+        if let Some(schemas) = &self.schemas {
+            schemas.check(&event)?;
+        }
         self.events.push(event);
         self.events.sort(); // keep it ordered
+        Ok(())
     }
 
     pub fn iter_chronological(&self) -> impl Iterator<Item = &ChronoEvent> {
@@ -48,208 +73,170 @@ impl Timeline {
         self.events.iter().filter(|e| &e.id == id).collect()
     }
 
-    pub fn playback(&self) -> HashMap<UvoxId, EntityState> {
-        let mut state = HashMap::new();
-        for e in self.iter_chronological() {
-            match &e.kind {
-                // === Core Lifecycle ===
-                EventKind::Spawn => {
-                    state.insert(
-                        e.id.clone(),
-                        EntityState {
-                            pos: Cartesian { x: 0.0, y: 0.0, z: 0.0 },
-                            alive: true,
-                            temperature: 20.0, // default room temp
-                            pressure: 101_325.0, // default 1 atm
-                        },
-                    );
-                }
+    pub fn query_by_source(&self, source: &str) -> Vec<&ChronoEvent> {
+        self.events
+            .iter()
+            .filter(|e| e.source.as_deref() == Some(source))
+            .collect()
+    }
 
-                EventKind::Despawn => {
-                    if let Some(s) = state.get_mut(&e.id) {
-                        s.alive = false;
-                    }
-                }
+    /// The timeline as it was known at `recorded`: only events recorded at or before it.
+    pub fn known_at(&self, recorded: DateTime<Utc>) -> Timeline {
+        Timeline {
+            events: self.events.iter().filter(|e| e.recorded_at <= recorded).cloned().collect(),
+            schemas: self.schemas.clone(),
+        }
+    }
 
-                // === Movement ===
-                EventKind::Move { offset } => {
-                    if let Some(s) = state.get_mut(&e.id) {
-                        s.pos.x += offset.x;
-                        s.pos.y += offset.y;
-                        s.pos.z += offset.z;
-                    }
-                }
-                EventKind::Teleport { new_pos } => {
-                    if let Some(s) = state.get_mut(&e.id) {
-                        s.pos = new_pos.clone();
-                    }
-                }
+    /// What was known at `recorded` about events occurring in `[start_ns, end_ns]`.
+    pub fn query_known_at(&self, recorded: DateTime<Utc>, start_ns: i64, end_ns: i64) -> Vec<&ChronoEvent> {
+        self.query_time_range(start_ns, end_ns)
+            .into_iter()
+            .filter(|e| e.recorded_at <= recorded)
+            .collect()
+    }
 
-                // === Environment ===
-                EventKind::TemperatureChange { delta_c } => {
-                    if let Some(s) = state.get_mut(&e.id) {
-                        s.temperature += delta_c;
-                    }
-                }
-                EventKind::PressureChange { delta_pa } => {
-                    if let Some(s) = state.get_mut(&e.id) {
-                        s.pressure += delta_pa;
-                    }
-                }
+    /// Retractions and corrections targeting `event_id`, in timeline order.
+    pub fn amendments_of(&self, event_id: Uuid) -> Vec<&ChronoEvent> {
+        self.events
+            .iter()
+            .filter(|e| e.amends() == Some(event_id))
+            .collect()
+    }
 
-                EventKind::Radiation { dose: _ } => {
-                    // TODO
-                }
-                EventKind::Shock { g: _ } => {
-                    // TODO
-                }
+    /// The events playback applies: those known under `config`, with
+    /// retracted events dropped and corrected ones swapped for their
+    /// replacement, in chronological order. The timeline itself is untouched.
+    pub fn effective_events(&self, config: &PlaybackConfig) -> Vec<ChronoEvent> {
+        let known: Vec<&ChronoEvent> = self.events.iter().filter(|e| config.knows(e)).collect();
+        let mut amendments: HashMap<Uuid, Vec<&ChronoEvent>> = HashMap::new();
+        for e in &known {
+            if let Some(target) = e.amends() {
+                amendments.entry(target).or_default().push(e);
+            }
+        }
 
-                // === Material / Integrity ===
-                EventKind::Degrade { rate: _ } => {
-                    // TODO
-                }
-                EventKind::Leak { severity: _ } => {
-                    // TODO
-                }
-                EventKind::Fracture { plane: _ } => {
-                    // TODO
-                }
+        let mut effective: Vec<ChronoEvent> = known
+            .iter()
+            .filter_map(|e| resolve_amendments(e, &amendments, 0))
+            .collect();
+        effective.sort();
+        effective
+    }
 
-                // === Interactions ===
-                EventKind::Bond { with: _ } => {
-                    // TODO: mark bonded state
-                }
-                EventKind::Unbond { from: _ } => {
-                    // TODO: mark bond broken
-                }
-                EventKind::Transfer { to: _, what: _, amount: _ } => {
-                    // TODO: transfer logic
-                }
+    pub fn find_event(&self, event_id: Uuid) -> Option<&ChronoEvent> {
+        self.events.iter().find(|e| e.event_id == event_id)
+    }
 
-                // === Wild Card ===
-                EventKind::Custom(_) => {
-                    // maybe log it or trigger hooks
-                }
+    pub fn query_by_correlation(&self, correlation_id: Uuid) -> Vec<&ChronoEvent> {
+        self.events
+            .iter()
+            .filter(|e| e.correlation_id == Some(correlation_id))
+            .collect()
+    }
+
+    /// Follow causation ids back from `event_id`, root cause first.
+    /// Stops at causes not present in this timeline.
+    pub fn causal_chain(&self, event_id: Uuid) -> Vec<&ChronoEvent> {
+        let mut chain = Vec::new();
+        let mut next = self.find_event(event_id);
+        while let Some(e) = next {
+            if chain.iter().any(|seen: &&ChronoEvent| seen.event_id == e.event_id) {
+                break; // guard against cycles
             }
+            chain.push(e);
+            next = e.causation_id.and_then(|cause| self.find_event(cause));
+        }
+        chain.reverse();
+        chain
+    }
+
+    pub fn playback(&self) -> HashMap<UvoxId, EntityState> {
+        self.playback_with(&PlaybackConfig::default())
+    }
+
+    /// Reconstruct final state, taking environment defaults from `config`.
+    pub fn playback_with(&self, config: &PlaybackConfig) -> HashMap<UvoxId, EntityState> {
+        let mut playback = Playback::new(config.clone());
+        for e in &self.effective_events(config) {
+            playback.apply(e);
         }
-        state
+        playback.into_state()
+    }
+
+    /// Reconstruct final state as it was known at `knowledge`, ignoring
+    /// events recorded after it.
+    pub fn playback_as_of(&self, knowledge: DateTime<Utc>) -> HashMap<UvoxId, EntityState> {
+        self.playback_with(&PlaybackConfig::new().with_as_of(knowledge))
+    }
+
+    /// Reconstruct state at `cutoff_ns` as it was known at `knowledge`.
+    pub fn playback_until_as_of(&self, cutoff_ns: i64, knowledge: DateTime<Utc>) -> HashMap<UvoxId, EntityState> {
+        self.playback_until_with(cutoff_ns, &PlaybackConfig::new().with_as_of(knowledge))
+    }
+
+    /// Reconstruct final state along with the `ConditionChange` events
+    /// derived whenever an entity crossed one of its thresholds.
+    pub fn playback_with_alerts(
+        &self,
+        config: &PlaybackConfig,
+    ) -> (HashMap<UvoxId, EntityState>, Vec<ChronoEvent>) {
+        let mut playback = Playback::new(config.clone());
+        for e in &self.effective_events(config) {
+            playback.apply(e);
+        }
+        let alerts = playback.drain_transitions();
+        (playback.into_state(), alerts)
     }
 
-    
     /// Reconstruct state up to a given time (with interpolation for Move)
     pub fn playback_until(&self, cutoff_ns: i64) -> HashMap<UvoxId, EntityState> {
-        let mut state: HashMap<UvoxId, EntityState> = HashMap::new();
+        self.playback_until_with(cutoff_ns, &PlaybackConfig::default())
+    }
+
+    /// Like [`Timeline::playback_until`], taking environment defaults from `config`.
+    pub fn playback_until_with(
+        &self,
+        cutoff_ns: i64,
+        config: &PlaybackConfig,
+    ) -> HashMap<UvoxId, EntityState> {
+        let mut playback = Playback::new(config.clone());
         let mut last_event_by_id: HashMap<UvoxId, &ChronoEvent> = HashMap::new();
 
-        for e in self.iter_chronological() {
+        let events = self.effective_events(config);
+        for e in &events {
             let t = e.t.ticks("nanoseconds");
 
             if t > cutoff_ns {
                 // Handle interpolation between two Move events
-                if let Some(prev) = last_event_by_id.get(&e.id) {
-                    if let (EventKind::Move { offset: prev_offset }, EventKind::Move { offset: next_offset }) =
+                if let Some(prev) = last_event_by_id.get(&e.id)
+                    && let (EventKind::Move { .. }, EventKind::Move { offset: next_offset }) =
                         (&prev.kind, &e.kind)
-                    {
-                        let t_prev = prev.t.ticks("nanoseconds");
-                        let t_next = t;
-                        let frac = (cutoff_ns - t_prev) as f64 / (t_next - t_prev) as f64;
-
-                        if let Some(s) = state.get_mut(&e.id) {
-                            s.pos = interpolate(prev_offset, next_offset, frac);
-                        }
+                {
+                    let t_prev = prev.t.ticks("nanoseconds");
+                    let t_next = t;
+                    let frac = (cutoff_ns - t_prev) as f64 / (t_next - t_prev) as f64;
+
+                    if let Some(s) = playback.state_mut().get_mut(&e.id) {
+                        let target = Cartesian {
+                            x: s.pos.x + next_offset.x,
+                            y: s.pos.y + next_offset.y,
+                            z: s.pos.z + next_offset.z,
+                        };
+                        s.pos = interpolate(&s.pos, &target, frac);
                     }
                 }
                 break; // stop at cutoff
             }
 
-            // Normal event application
-            match &e.kind {
-                // === Core Lifecycle ===
-                EventKind::Spawn => {
-                    state.insert(
-                        e.id.clone(),
-                        EntityState {
-                            pos: Cartesian { x: 0.0, y: 0.0, z: 0.0 },
-                            alive: true,
-                            temperature: 20.0,   // default °C
-                            pressure: 101_325.0, // default Pa
-                        },
-                    );
-                }
-                EventKind::Despawn => {
-                    if let Some(s) = state.get_mut(&e.id) {
-                        s.alive = false;
-                    }
-                }
-
-                // === Movement ===
-                EventKind::Move { offset } => {
-                    if let Some(s) = state.get_mut(&e.id) {
-                        s.pos.x += offset.x;
-                        s.pos.y += offset.y;
-                        s.pos.z += offset.z;
-                    }
-                }
-                EventKind::Teleport { new_pos } => {
-                    if let Some(s) = state.get_mut(&e.id) {
-                        s.pos = new_pos.clone();
-                    }
-                }
-
-                // === Environment ===
-                EventKind::TemperatureChange { delta_c } => {
-                    if let Some(s) = state.get_mut(&e.id) {
-                        s.temperature += delta_c;
-                    }
-                }
-                EventKind::PressureChange { delta_pa } => {
-                    if let Some(s) = state.get_mut(&e.id) {
-                        s.pressure += delta_pa;
-                    }
-                }
-
-                EventKind::Radiation { dose: _ } => {
-                    // TODO: accumulate radiation dose
-                }
-                EventKind::Shock { g: _ } => {
-                    // TODO: apply shock/damage
-                }
-
-                // === Material / Integrity ===
-                EventKind::Degrade { rate: _ } => {
-                    // TODO: mark progressive degradation
-                }
-                EventKind::Leak { severity: _ } => {
-                    // TODO: track fluid/gas loss
-                }
-                EventKind::Fracture { plane: _ } => {
-                    // TODO: mark fracture in state
-                }
-
-                // === Interactions ===
-                EventKind::Bond { with: _ } => {
-                    // TODO: link entities
-                }
-                EventKind::Unbond { from: _ } => {
-                    // TODO: unlink entities
-                }
-                EventKind::Transfer { to: _, what: _, amount: _ } => {
-                    // TODO: handle resource transfer
-                }
-
-                // === Wild Card ===
-                EventKind::Custom(_) => {
-                    // TODO: maybe just record it
-                }
-            }
-
-            last_event_by_id.insert(e.id.clone(), e);
+            playback.apply(e);
+            last_event_by_id.insert(e.id, e);
         }
 
-        state
+        playback.advance_to(cutoff_ns);
+        playback.into_state()
     }
 
-
     pub fn len(&self) -> usize {
         self.events.len()
     }
@@ -261,6 +248,72 @@ impl Timeline {
 
 // ===== Helper =====
 
+/// Amendment chains deeper than this are treated as unresolved.
+const MAX_AMENDMENT_DEPTH: usize = 32;
+
+/// The latest amendment of `event_id` that hasn't itself been retracted.
+fn latest_amendment<'a>(
+    event_id: Uuid,
+    amendments: &HashMap<Uuid, Vec<&'a ChronoEvent>>,
+    depth: usize,
+) -> Option<&'a ChronoEvent> {
+    if depth > MAX_AMENDMENT_DEPTH {
+        return None;
+    }
+    amendments.get(&event_id)?.iter().rev().copied().find(|a| {
+        !matches!(
+            latest_amendment(a.event_id, amendments, depth + 1).map(|r| &r.kind),
+            Some(EventKind::Retract { .. })
+        )
+    })
+}
+
+/// What playback should apply in place of `e`: nothing if retracted, the
+/// (recursively resolved) replacement if corrected, otherwise `e` itself.
+/// Amendments that have themselves been amended contribute nothing; their
+/// effect is carried by the event they target.
+fn resolve_amendments(
+    e: &ChronoEvent,
+    amendments: &HashMap<Uuid, Vec<&ChronoEvent>>,
+    depth: usize,
+) -> Option<ChronoEvent> {
+    if depth > MAX_AMENDMENT_DEPTH {
+        return None;
+    }
+    let latest = latest_amendment(e.event_id, amendments, depth);
+    if e.amends().is_some() {
+        return latest.is_none().then(|| e.clone());
+    }
+    match latest {
+        Some(a @ ChronoEvent { kind: EventKind::Correct { .. }, .. }) => {
+            resolve_amendments(final_replacement(a, amendments, depth + 1)?, amendments, depth + 1)
+        }
+        Some(_) => None,
+        None => Some(e.clone()),
+    }
+}
+
+/// The replacement a correction finally stands for: if the correction was
+/// itself corrected, the replacement at the end of that chain.
+fn final_replacement<'a>(
+    correction: &'a ChronoEvent,
+    amendments: &HashMap<Uuid, Vec<&'a ChronoEvent>>,
+    depth: usize,
+) -> Option<&'a ChronoEvent> {
+    if depth > MAX_AMENDMENT_DEPTH {
+        return None;
+    }
+    match latest_amendment(correction.event_id, amendments, depth) {
+        Some(next @ ChronoEvent { kind: EventKind::Correct { .. }, .. }) => {
+            final_replacement(next, amendments, depth + 1)
+        }
+        _ => match &correction.kind {
+            EventKind::Correct { replacement, .. } => Some(replacement),
+            _ => None,
+        },
+    }
+}
+
 fn interpolate(prev: &Cartesian, next: &Cartesian, frac: f64) -> Cartesian {
     Cartesian {
         x: prev.x + frac * (next.x - prev.x),
@@ -280,7 +333,7 @@ impl Eq for ChronoEvent {}
 
 impl PartialOrd for ChronoEvent {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.t.ticks("nanoseconds").cmp(&other.t.ticks("nanoseconds")))
+        Some(self.cmp(other))
     }
 }
 impl Ord for ChronoEvent {
@@ -0,0 +1,257 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::Value;
+use uuid::Uuid;
+use crate::{ChronoEvent, EventKind, UvoxId, TimeDelta, Result};
+use crate::error::ChronovoxError;
+
+/// Schema version written on every serialized or persisted `ChronoEvent`.
+pub const EVENT_VERSION: u32 = 3;
+
+/// Transforms an event of version `n` into version `n + 1`.
+type Upcaster = fn(Value) -> Result<Value>;
+
+/// `UPCASTERS[n]` upgrades version `n`; the chain ends at `EVENT_VERSION`.
+const UPCASTERS: [Upcaster; EVENT_VERSION as usize] = [v0_to_v1, v1_to_v2, v2_to_v3];
+
+/// Bring a serialized event up to `EVENT_VERSION`.
+/// Events without a `version` are treated as version 0.
+pub fn upcast(mut value: Value) -> Result<Value> {
+    let mut version = value.get("version").and_then(Value::as_u64).unwrap_or(0) as u32;
+    if version > EVENT_VERSION {
+        return Err(ChronovoxError::UnsupportedVersion(version));
+    }
+    while version < EVENT_VERSION {
+        value = UPCASTERS[version as usize](value)?;
+        version += 1;
+        value["version"] = version.into();
+    }
+    Ok(value)
+}
+
+/// v0 events predate the version tag. Persisted v0 rows stored the kind as
+/// its `Debug` text (`TemperatureChange { delta_c: 10.0 }`), which is parsed
+/// back into the serde layout; serialized v0 events already use it.
+fn v0_to_v1(mut value: Value) -> Result<Value> {
+    if let Some(text) = value.get("kind").and_then(Value::as_str) {
+        value["kind"] = parse_debug_kind(text).ok_or_else(|| ChronovoxError::Decode {
+            row_id: None,
+            reason: format!("unrecognised legacy kind {text:?}"),
+        })?;
+    }
+    Ok(value)
+}
+
+/// Parse the derived `Debug` output of an `EventKind` into the JSON its
+/// serde impl reads: `Name` → `"Name"`, `Name(v)` → `{"Name": v}` and
+/// `Name { f: v, .. }` → `{"Name": {"f": v, ..}}`.
+fn parse_debug_kind(text: &str) -> Option<Value> {
+    let mut parser = DebugParser { text, pos: 0 };
+    let name = parser.ident()?;
+    let kind = match parser.peek() {
+        None => Value::String(name.to_string()),
+        Some(_) => serde_json::json!({ name: parser.body()? }),
+    };
+    parser.peek().is_none().then_some(kind)
+}
+
+/// Recursive-descent reader for the subset of `Debug` syntax event kinds
+/// print: idents, structs, one-field tuples, strings, numbers and uuids.
+struct DebugParser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> DebugParser<'a> {
+    /// The next non-whitespace char, without consuming it.
+    fn peek(&mut self) -> Option<char> {
+        let rest = &self.text[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+        self.text[self.pos..].chars().next()
+    }
+
+    fn eat(&mut self, c: char) -> Option<()> {
+        (self.peek()? == c).then(|| self.pos += c.len_utf8())
+    }
+
+    /// A run of identifier characters; `-` keeps uuids in one token.
+    fn token(&mut self) -> Option<&'a str> {
+        self.peek()?;
+        let rest = &self.text[self.pos..];
+        let len = rest.find(|c: char| !(c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | '+'))).unwrap_or(rest.len());
+        self.pos += len;
+        (len > 0).then(|| &rest[..len])
+    }
+
+    fn ident(&mut self) -> Option<&'a str> {
+        self.token().filter(|t| t.chars().all(|c| c.is_alphanumeric() || c == '_'))
+    }
+
+    /// `{ field: value, .. }` or `(value)` following a name.
+    fn body(&mut self) -> Option<Value> {
+        if self.eat('(').is_some() {
+            let value = self.value()?;
+            self.eat(')')?;
+            return Some(value);
+        }
+        self.eat('{')?;
+        let mut fields = serde_json::Map::new();
+        while self.eat('}').is_none() {
+            let field = self.ident()?;
+            self.eat(':')?;
+            fields.insert(field.to_string(), self.value()?);
+            if self.eat(',').is_none() {
+                self.eat('}')?;
+                break;
+            }
+        }
+        Some(Value::Object(fields))
+    }
+
+    fn value(&mut self) -> Option<Value> {
+        if self.peek()? == '"' {
+            return self.string();
+        }
+        let token = self.token()?;
+        if let Ok(n) = token.parse::<i64>() {
+            return Some(n.into());
+        }
+        if let Ok(x) = token.parse::<f64>() {
+            return serde_json::Number::from_f64(x).map(Value::Number);
+        }
+        if let Ok(id) = token.parse::<Uuid>() {
+            return Some(Value::String(id.to_string()));
+        }
+        match token {
+            "true" | "false" => Some(Value::Bool(token == "true")),
+            // A nested struct (e.g. `Cartesian { .. }`) is just its fields
+            _ if self.peek() == Some('{') => self.body(),
+            _ if self.peek() == Some('(') => Some(serde_json::json!({ token: self.body()? })),
+            _ => Some(Value::String(token.to_string())),
+        }
+    }
+
+    /// A quoted string with Rust's `Debug` escapes.
+    fn string(&mut self) -> Option<Value> {
+        self.eat('"')?;
+        let mut out = String::new();
+        let mut chars = self.text[self.pos..].char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.pos += i + 1;
+                    return Some(Value::String(out));
+                }
+                '\\' => out.push(match chars.next()?.1 {
+                    'n' => '\n',
+                    't' => '\t',
+                    'r' => '\r',
+                    '0' => '\0',
+                    'u' => {
+                        let hex: String = chars.by_ref().map(|(_, c)| c).skip(1).take_while(|c| *c != '}').collect();
+                        char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?
+                    }
+                    other => other,
+                }),
+                c => out.push(c),
+            }
+        }
+        None
+    }
+}
+
+/// Namespace for ids derived from the content of pre-id events.
+const LEGACY_ID_NAMESPACE: Uuid = Uuid::from_u128(0x1ecc5504_386c_4b12_b466_69f482e3dce0);
+
+/// v2 gave events their own id. Older events get one derived from their
+/// content, so reading the same event twice yields the same id and it can
+/// still be amended, merged and diffed by id (identical pre-id events
+/// share one). No causation or correlation.
+fn v1_to_v2(mut value: Value) -> Result<Value> {
+    if value.get("event_id").is_none_or(Value::is_null) {
+        let id = Uuid::new_v5(&LEGACY_ID_NAMESPACE, canonical(&value).as_bytes());
+        value["event_id"] = serde_json::to_value(id)?;
+    }
+    Ok(value)
+}
+
+/// JSON text independent of key order, without top-level nulls or the
+/// version tag, so equal events hash alike however they were serialized.
+fn canonical(value: &Value) -> String {
+    fn write(value: &Value, out: &mut String) {
+        match value {
+            Value::Object(map) => {
+                let mut keys: Vec<&String> = map.keys().collect();
+                keys.sort();
+                out.push('{');
+                for (i, key) in keys.into_iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    out.push_str(&Value::String(key.clone()).to_string());
+                    out.push(':');
+                    write(&map[key], out);
+                }
+                out.push('}');
+            }
+            Value::Array(items) => {
+                out.push('[');
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    write(item, out);
+                }
+                out.push(']');
+            }
+            other => out.push_str(&other.to_string()),
+        }
+    }
+
+    let mut content = value.clone();
+    if let Value::Object(map) = &mut content {
+        map.retain(|key, v| key != "version" && !v.is_null());
+    }
+    let mut out = String::new();
+    write(&content, &mut out);
+    out
+}
+
+/// v3 added provenance. When older events were recorded is unknown, so
+/// they count as known since the Unix epoch.
+fn v2_to_v3(mut value: Value) -> Result<Value> {
+    if value.get("recorded_at").is_none_or(Value::is_null) {
+        value["recorded_at"] = serde_json::to_value(DateTime::<Utc>::UNIX_EPOCH)?;
+    }
+    Ok(value)
+}
+
+/// The current-version layout `ChronoEvent` deserializes from after upcasting.
+#[derive(Deserialize)]
+#[serde(remote = "ChronoEvent")]
+struct Current {
+    version: u32,
+    event_id: Uuid,
+    #[serde(default)]
+    causation_id: Option<Uuid>,
+    #[serde(default)]
+    correlation_id: Option<Uuid>,
+    id: UvoxId,
+    t: TimeDelta,
+    recorded_at: DateTime<Utc>,
+    #[serde(default)]
+    source: Option<String>,
+    #[serde(default)]
+    author: Option<String>,
+    kind: EventKind,
+    #[serde(default)]
+    payload: Option<Value>,
+}
+
+impl TryFrom<Value> for ChronoEvent {
+    type Error = ChronovoxError;
+
+    fn try_from(value: Value) -> Result<Self> {
+        Ok(Current::deserialize(upcast(value)?)?)
+    }
+}
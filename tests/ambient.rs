@@ -0,0 +1,73 @@
+use chronovox::{Cartesian, ChronoEvent, EventKind, Region, SpawnState, Timeline};
+use uvoxid::UvoxId;
+use tdt::core::TimeDelta;
+
+fn at(id: UvoxId, nanos: i64, kind: EventKind) -> ChronoEvent {
+    ChronoEvent::new(id, TimeDelta::from_ticks(nanos, "nanoseconds"), kind)
+}
+
+fn spawn_at(id: UvoxId, pos: Cartesian) -> ChronoEvent {
+    let init = SpawnState { pos: Some(pos), ..Default::default() };
+    ChronoEvent { payload: Some(init.to_payload()), ..at(id, 0, EventKind::Spawn) }
+}
+
+fn room() -> Region {
+    Region::Box {
+        frame_id: 0,
+        min: Cartesian { x: 0.0, y: 0.0, z: 0.0 },
+        max: Cartesian { x: 5.0, y: 5.0, z: 3.0 },
+    }
+}
+
+#[test]
+fn ambient_applies_to_entities_inside_region_while_active() {
+    let pipe = UvoxId::earth(1, 0, 0);
+    let shed = UvoxId::earth(2, 0, 0);
+    let heatwave = UvoxId::earth(0, 0, 0);
+
+    let mut timeline = Timeline::new();
+    timeline.insert(spawn_at(pipe, Cartesian { x: 1.0, y: 1.0, z: 1.0 })).unwrap();
+    timeline.insert(spawn_at(shed, Cartesian { x: 50.0, y: 1.0, z: 1.0 })).unwrap();
+    timeline.insert(at(heatwave, 100, EventKind::Ambient {
+        region: room(),
+        temperature_c: Some(35.0),
+        pressure_pa: None,
+        until: Some(TimeDelta::from_ticks(200, "nanoseconds")),
+    })).unwrap();
+    // The pipe warms up inside the room, then a local change is overridden by the field
+    timeline.insert(at(pipe, 150, EventKind::TemperatureChange { delta_c: -10.0 })).unwrap();
+
+    let mid = timeline.playback_until(160);
+    assert_eq!(mid[&pipe].temperature, 35.0);
+    assert_eq!(mid[&shed].temperature, 20.0);
+    assert_eq!(mid[&pipe].pressure, 101_325.0);
+
+    // Before the field starts nothing has changed
+    let before = timeline.playback_until(50);
+    assert_eq!(before[&pipe].temperature, 20.0);
+}
+
+#[test]
+fn ambient_reaches_entities_that_move_in_and_stops_after_until() {
+    let pipe = UvoxId::earth(1, 0, 0);
+    let heatwave = UvoxId::earth(0, 0, 0);
+
+    let mut timeline = Timeline::new();
+    timeline.insert(spawn_at(pipe, Cartesian { x: 50.0, y: 1.0, z: 1.0 })).unwrap();
+    timeline.insert(at(heatwave, 100, EventKind::Ambient {
+        region: room(),
+        temperature_c: Some(35.0),
+        pressure_pa: Some(90_000.0),
+        until: Some(TimeDelta::from_ticks(200, "nanoseconds")),
+    })).unwrap();
+    timeline.insert(at(pipe, 150, EventKind::Teleport { new_pos: Cartesian { x: 2.0, y: 2.0, z: 1.0 } })).unwrap();
+    timeline.insert(at(pipe, 250, EventKind::TemperatureChange { delta_c: 5.0 })).unwrap();
+
+    let inside = timeline.playback_until(160);
+    assert_eq!(inside[&pipe].temperature, 35.0);
+    assert_eq!(inside[&pipe].pressure, 90_000.0);
+
+    // After the field ends, later changes are no longer overridden
+    let after = timeline.playback();
+    assert_eq!(after[&pipe].temperature, 40.0);
+}
@@ -0,0 +1,89 @@
+#[cfg(feature = "supabase")]
+mod common;
+
+use chronovox::{ChronoEvent, EventKind, Timeline};
+#[cfg(feature = "supabase")]
+use chronovox::{fetch_events_for_entity, insert_event_for_entity};
+use tdt::core::TimeDelta;
+#[cfg(feature = "supabase")]
+use uuid::Uuid;
+use uvoxid::UvoxId;
+
+fn pipe() -> UvoxId {
+    UvoxId::earth(1, 0, 0)
+}
+
+fn at(nanos: i64, kind: EventKind) -> ChronoEvent {
+    ChronoEvent::new(pipe(), TimeDelta::from_ticks(nanos, "nanoseconds"), kind)
+}
+
+fn ns(nanos: i64) -> TimeDelta {
+    TimeDelta::from_ticks(nanos, "nanoseconds")
+}
+
+#[test]
+fn retracted_events_are_skipped_but_kept() {
+    let bogus = at(10, EventKind::TemperatureChange { delta_c: 80.0 });
+    let mut timeline = Timeline::new();
+    timeline.insert(at(0, EventKind::Spawn)).unwrap();
+    timeline.insert(bogus.clone()).unwrap();
+    timeline.insert(ChronoEvent::retraction_of(&bogus, ns(20))).unwrap();
+
+    assert_eq!(timeline.playback()[&pipe()].temperature, 20.0);
+    // Even playback before the retraction's own time excludes it
+    assert_eq!(timeline.playback_until(15)[&pipe()].temperature, 20.0);
+    // The audit trail still has both
+    assert_eq!(timeline.len(), 3);
+    assert_eq!(timeline.amendments_of(bogus.event_id).len(), 1);
+}
+
+#[test]
+fn corrections_substitute_and_can_be_undone() {
+    let misread = at(10, EventKind::TemperatureChange { delta_c: 80.0 });
+    let correction = ChronoEvent::correction_of(&misread, ns(20), at(10, EventKind::TemperatureChange { delta_c: 8.0 }));
+
+    let mut timeline = Timeline::new();
+    timeline.insert(at(0, EventKind::Spawn)).unwrap();
+    timeline.insert(misread.clone()).unwrap();
+    timeline.insert(correction.clone()).unwrap();
+    assert_eq!(timeline.playback()[&pipe()].temperature, 28.0);
+
+    // Retracting the correction restores the original reading
+    timeline.insert(ChronoEvent::retraction_of(&correction, ns(30))).unwrap();
+    assert_eq!(timeline.playback()[&pipe()].temperature, 100.0);
+}
+
+#[test]
+fn corrections_of_corrections_apply_only_the_last_replacement() {
+    let misread = at(10, EventKind::TemperatureChange { delta_c: 80.0 });
+    let first = ChronoEvent::correction_of(&misread, ns(20), at(10, EventKind::TemperatureChange { delta_c: 8.0 }));
+    let second = ChronoEvent::correction_of(&first, ns(30), at(10, EventKind::TemperatureChange { delta_c: 4.0 }));
+
+    let mut timeline = Timeline::new();
+    timeline.insert(at(0, EventKind::Spawn)).unwrap();
+    timeline.insert(misread).unwrap();
+    timeline.insert(first).unwrap();
+    timeline.insert(second.clone()).unwrap();
+    assert_eq!(timeline.playback()[&pipe()].temperature, 24.0);
+
+    // Retracting the second correction falls back to the first
+    timeline.insert(ChronoEvent::retraction_of(&second, ns(40))).unwrap();
+    assert_eq!(timeline.playback()[&pipe()].temperature, 28.0);
+}
+
+#[cfg(feature = "supabase")]
+#[tokio::test]
+async fn amendments_are_appended_not_mutated_in_persistence() {
+    let (mock, supa) = common::MockSupabase::start().await;
+    let entity_id = Uuid::new_v4();
+    let misread = at(10, EventKind::TemperatureChange { delta_c: 80.0 });
+    let correction = ChronoEvent::correction_of(&misread, ns(20), at(10, EventKind::TemperatureChange { delta_c: 8.0 }));
+
+    for e in [at(0, EventKind::Spawn), misread, correction] {
+        insert_event_for_entity(&supa, entity_id, &e).await.unwrap();
+    }
+    assert_eq!(mock.rows().len(), 3);
+
+    let timeline = fetch_events_for_entity(&supa, entity_id).await.unwrap();
+    assert_eq!(timeline.playback()[&pipe()].temperature, 28.0);
+}
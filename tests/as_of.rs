@@ -0,0 +1,38 @@
+use chrono::{DateTime, Duration, Utc};
+use chronovox::{ChronoEvent, EventKind, Timeline};
+use tdt::core::TimeDelta;
+use uvoxid::UvoxId;
+
+fn recorded(nanos: i64, kind: EventKind, recorded_at: DateTime<Utc>) -> ChronoEvent {
+    ChronoEvent::new(UvoxId::earth(1, 0, 0), TimeDelta::from_ticks(nanos, "nanoseconds"), kind)
+        .with_recorded_at(recorded_at)
+}
+
+#[test]
+fn as_of_playback_ignores_late_arriving_events() {
+    let pipe = UvoxId::earth(1, 0, 0);
+    let decision = Utc::now();
+    let before = decision - Duration::minutes(10);
+    let after = decision + Duration::minutes(10);
+
+    let mut timeline = Timeline::new();
+    timeline.insert(recorded(0, EventKind::Spawn, before)).unwrap();
+    timeline.insert(recorded(100, EventKind::TemperatureChange { delta_c: 5.0 }, before)).unwrap();
+    // The sensor's reading of an earlier drop only arrived after the decision
+    timeline.insert(recorded(50, EventKind::TemperatureChange { delta_c: -30.0 }, after)).unwrap();
+
+    assert_eq!(timeline.playback_as_of(decision)[&pipe].temperature, 25.0);
+    assert_eq!(timeline.playback()[&pipe].temperature, -5.0);
+
+    assert_eq!(timeline.playback_until_as_of(75, decision)[&pipe].temperature, 20.0);
+    assert_eq!(timeline.playback_until(75)[&pipe].temperature, -10.0);
+}
+
+#[test]
+fn nothing_is_known_before_it_was_recorded() {
+    let now = Utc::now();
+    let mut timeline = Timeline::new();
+    timeline.insert(recorded(0, EventKind::Spawn, now)).unwrap();
+
+    assert!(timeline.playback_as_of(now - Duration::seconds(1)).is_empty());
+}
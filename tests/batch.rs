@@ -0,0 +1,74 @@
+#![cfg(feature = "supabase")]
+
+mod common;
+
+use chronovox::persist::INSERT_BATCH_SIZE;
+use chronovox::{fetch_events_for_entities, fetch_events_for_entity, insert_events, ChronoEvent, EventKind, Timeline};
+use tdt::core::TimeDelta;
+use uuid::Uuid;
+use uvoxid::UvoxId;
+
+fn history(n: usize) -> Timeline {
+    let id = UvoxId::earth(1, 0, 0);
+    let mut timeline = Timeline::new();
+    timeline.push(ChronoEvent::new(id, TimeDelta::from_ticks(0, "nanoseconds"), EventKind::Spawn));
+    for i in 1..n {
+        timeline.push(ChronoEvent::new(
+            id,
+            TimeDelta::from_ticks(i as i64, "nanoseconds"),
+            EventKind::TemperatureChange { delta_c: 0.01 },
+        ));
+    }
+    timeline
+}
+
+#[tokio::test]
+async fn insert_events_batches_and_keeps_order() {
+    let (mock, supa) = common::MockSupabase::start().await;
+    let timeline = history(INSERT_BATCH_SIZE * 2 + 1);
+
+    let ids = insert_events(&supa, Uuid::new_v4(), &timeline).await.unwrap();
+
+    let expected: Vec<Uuid> = timeline.iter_chronological().map(|e| e.event_id).collect();
+    assert_eq!(ids, expected);
+    assert_eq!(mock.requests.lock().unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn fetch_events_for_entities_groups_by_entity() {
+    let (mock, supa) = common::MockSupabase::start().await;
+    let pipe = Uuid::new_v4();
+    let valve = Uuid::new_v4();
+    let idle = Uuid::new_v4();
+    let unrelated = Uuid::new_v4();
+    insert_events(&supa, pipe, &history(3)).await.unwrap();
+    insert_events(&supa, valve, history(2).events.as_slice()).await.unwrap();
+    insert_events(&supa, unrelated, &history(4)).await.unwrap();
+
+    let timelines = fetch_events_for_entities(&supa, &[pipe, valve, idle]).await.unwrap();
+
+    assert_eq!(timelines.len(), 3);
+    assert_eq!(timelines[&pipe].len(), 3);
+    assert_eq!(timelines[&valve].len(), 2);
+    assert!(timelines[&idle].is_empty());
+    // Three inserts, then a page of rows and the empty page ending it
+    assert_eq!(mock.requests.lock().unwrap().len(), 5);
+}
+
+#[tokio::test]
+async fn fetches_page_past_the_server_row_cap() {
+    let (mock, supa) = common::MockSupabase::start().await;
+    mock.set_max_rows(7);
+    let pipe = Uuid::new_v4();
+    let valve = Uuid::new_v4();
+    insert_events(&supa, pipe, &history(20)).await.unwrap();
+    insert_events(&supa, valve, &history(15)).await.unwrap();
+
+    let timelines = fetch_events_for_entities(&supa, &[pipe, valve]).await.unwrap();
+    assert_eq!(timelines[&pipe].len(), 20);
+    assert_eq!(timelines[&valve].len(), 15);
+
+    let timeline = fetch_events_for_entity(&supa, pipe).await.unwrap();
+    let ticks: Vec<i64> = timeline.iter_chronological().map(|e| e.t.ticks("nanoseconds")).collect();
+    assert_eq!(ticks, (0..20).collect::<Vec<_>>());
+}
@@ -0,0 +1,291 @@
+// tests/common/mod.rs
+//
+// A tiny in-memory stand-in for Supabase's PostgREST API, enough for the
+// persistence tests to run without a real project.
+#![allow(dead_code)]
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use serde_json::{json, Value};
+use supabasic::Supabase;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+
+#[derive(Clone, Default)]
+pub struct MockSupabase {
+    pub rows: Arc<Mutex<Vec<Value>>>,
+    pub requests: Arc<Mutex<Vec<String>>>,
+    /// Canned error responses served, in order, before any real handling.
+    pub failures: Arc<Mutex<VecDeque<(&'static str, String)>>>,
+    /// Like `failures`, but only served to inserts.
+    pub insert_failures: Arc<Mutex<VecDeque<(&'static str, String)>>>,
+    /// PostgREST's `max-rows`; `None` uses Supabase's default.
+    pub max_rows: Arc<Mutex<Option<usize>>>,
+}
+
+/// Rows Supabase returns per response unless configured otherwise.
+const DEFAULT_MAX_ROWS: usize = 1000;
+
+impl MockSupabase {
+    /// Start serving on a random local port; returns the store and a client for it.
+    pub async fn start() -> (MockSupabase, Supabase) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let mock = MockSupabase::default();
+
+        let server = mock.clone();
+        tokio::spawn(async move {
+            loop {
+                let Ok((stream, _)) = listener.accept().await else { break };
+                let server = server.clone();
+                tokio::spawn(async move {
+                    let mut reader = BufReader::new(stream);
+                    let mut request_line = String::new();
+                    reader.read_line(&mut request_line).await.unwrap();
+
+                    let mut content_length = 0;
+                    loop {
+                        let mut line = String::new();
+                        reader.read_line(&mut line).await.unwrap();
+                        if line == "\r\n" || line.is_empty() {
+                            break;
+                        }
+                        if let Some((name, value)) = line.split_once(':')
+                            && name.eq_ignore_ascii_case("content-length")
+                        {
+                            content_length = value.trim().parse().unwrap();
+                        }
+                    }
+                    let mut body = vec![0; content_length];
+                    reader.read_exact(&mut body).await.unwrap();
+
+                    server.requests.lock().unwrap().push(request_line.trim().to_string());
+                    let mut failure = server.failures.lock().unwrap().pop_front();
+                    if failure.is_none() && request_line.starts_with("POST") {
+                        failure = server.insert_failures.lock().unwrap().pop_front();
+                    }
+                    let (status, response) = match failure {
+                        Some(failure) => failure,
+                        None => {
+                            let (status, response) = server.handle(request_line.trim(), &body);
+                            (status, response.to_string())
+                        }
+                    };
+                    let reply = format!(
+                        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+                        response.len()
+                    );
+                    reader.get_mut().write_all(reply.as_bytes()).await.unwrap();
+                });
+            }
+        });
+
+        (mock, Supabase::new(&url, "test-key"))
+    }
+
+    pub fn rows(&self) -> Vec<Value> {
+        self.rows.lock().unwrap().clone()
+    }
+
+    /// Cap every response at `max_rows` rows, like PostgREST does silently.
+    pub fn set_max_rows(&self, max_rows: usize) {
+        *self.max_rows.lock().unwrap() = Some(max_rows);
+    }
+
+    /// Answer the next request with `status` and `body` instead.
+    pub fn fail_next(&self, status: &'static str, body: Value) {
+        self.fail_next_raw(status, &body.to_string());
+    }
+
+    /// Like [`fail_next`](Self::fail_next) with a body that needn't be JSON,
+    /// as a gateway or proxy in front of PostgREST might send.
+    pub fn fail_next_raw(&self, status: &'static str, body: &str) {
+        self.failures.lock().unwrap().push_back((status, body.to_string()));
+    }
+
+    /// Answer the next insert with `status` and `body`, letting other
+    /// requests through.
+    pub fn fail_next_insert(&self, status: &'static str, body: Value) {
+        self.insert_failures.lock().unwrap().push_back((status, body.to_string()));
+    }
+
+    fn handle(&self, request_line: &str, body: &[u8]) -> (&'static str, Value) {
+        let mut parts = request_line.split_whitespace();
+        let method = parts.next().unwrap_or_default();
+        let target = parts.next().unwrap_or_default();
+        let query = target.split_once('?').map(|(_, q)| q).unwrap_or_default();
+
+        match method {
+            "POST" => {
+                let new_rows: Vec<Value> = serde_json::from_slice(body).unwrap();
+                let mut rows = self.rows.lock().unwrap();
+                // Like Postgres, a duplicate primary key rejects the whole statement
+                let duplicate = new_rows.iter().any(|new| {
+                    new.get("id").is_some_and(|id| !id.is_null() && rows.iter().any(|row| &row["id"] == id))
+                });
+                if duplicate {
+                    return (
+                        "409 Conflict",
+                        json!({
+                            "code": "23505",
+                            "message": "duplicate key value violates unique constraint \"events_pkey\"",
+                        }),
+                    );
+                }
+                let mut inserted = Vec::new();
+                for mut row in new_rows {
+                    if row.get("id").is_none_or(Value::is_null) {
+                        row["id"] = json!(uuid::Uuid::new_v4());
+                    }
+                    // Column default, as in migrations/0001_create_events.sql
+                    if row.get("timestamp").is_none() {
+                        row["timestamp"] = json!(chrono::Utc::now());
+                    }
+                    rows.push(row.clone());
+                    inserted.push(row);
+                }
+                ("201 Created", Value::Array(inserted))
+            }
+            "GET" => {
+                let rows = self.rows.lock().unwrap();
+                let mut matching: Vec<Value> = rows
+                    .iter()
+                    .filter(|row| matches_filters(row, query))
+                    .cloned()
+                    .collect();
+                apply_order_and_limit(&mut matching, query);
+                matching.truncate(self.max_rows.lock().unwrap().unwrap_or(DEFAULT_MAX_ROWS));
+                let matching = matching.into_iter().map(|row| project(row, query)).collect();
+                ("200 OK", Value::Array(matching))
+            }
+            _ => ("405 Method Not Allowed", json!({ "message": "unsupported" })),
+        }
+    }
+}
+
+/// A client for a port nothing listens on.
+pub async fn offline() -> Supabase {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    drop(listener);
+    Supabase::new(&url, "test-key")
+}
+
+/// Apply PostgREST `column=op.value` filters (eq, gt, gte, lt, lte, in)
+/// and `or=(...)` groups to a row.
+fn matches_filters(row: &Value, query: &str) -> bool {
+    query_pairs(query).all(|(column, filter)| match column {
+        "select" | "order" | "limit" => true,
+        "or" => matches_term(row, &format!("or{filter}")),
+        _ => matches_op(row, column, filter),
+    })
+}
+
+fn query_pairs(query: &str) -> impl Iterator<Item = (&str, &str)> {
+    query.split('&').filter_map(|pair| pair.split_once('='))
+}
+
+/// A term inside a logical group: `column.op.value` or `and(...)`/`or(...)`.
+fn matches_term(row: &Value, term: &str) -> bool {
+    if let Some(inner) = term.strip_prefix("and(").and_then(|t| t.strip_suffix(')')) {
+        return split_terms(inner).iter().all(|t| matches_term(row, t));
+    }
+    if let Some(inner) = term.strip_prefix("or(").and_then(|t| t.strip_suffix(')')) {
+        return split_terms(inner).iter().any(|t| matches_term(row, t));
+    }
+    match term.split_once('.') {
+        Some((column, filter)) => matches_op(row, column, filter),
+        None => true,
+    }
+}
+
+/// Split on top-level commas.
+fn split_terms(group: &str) -> Vec<&str> {
+    let mut terms = Vec::new();
+    let (mut depth, mut start) = (0, 0);
+    for (i, c) in group.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                terms.push(&group[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    terms.push(&group[start..]);
+    terms
+}
+
+fn matches_op(row: &Value, column: &str, filter: &str) -> bool {
+    let Some((op, value)) = filter.split_once('.') else { return true };
+    let Some(field) = row.get(column) else { return true };
+    match op {
+        "eq" => value_text(field) == value,
+        "gt" => compare(field, value).is_gt(),
+        "gte" => compare(field, value).is_ge(),
+        "lt" => compare(field, value).is_lt(),
+        "lte" => compare(field, value).is_le(),
+        "in" => {
+            let text = value_text(field);
+            value.trim_matches(|c| c == '(' || c == ')').split(',').any(|v| v == text)
+        }
+        _ => true,
+    }
+}
+
+/// Apply `order=col.asc,col.desc` and `limit=n`.
+fn apply_order_and_limit(rows: &mut Vec<Value>, query: &str) {
+    for (key, value) in query_pairs(query) {
+        match key {
+            "order" => {
+                let keys: Vec<(&str, bool)> = value
+                    .split(',')
+                    .map(|k| match k.split_once('.') {
+                        Some((column, dir)) => (column, dir == "desc"),
+                        None => (k, false),
+                    })
+                    .collect();
+                rows.sort_by(|a, b| {
+                    keys.iter()
+                        .map(|(column, desc)| {
+                            let o = compare(&a[*column], &value_text(&b[*column]));
+                            if *desc { o.reverse() } else { o }
+                        })
+                        .find(|o| o.is_ne())
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                });
+            }
+            "limit" => rows.truncate(value.parse().unwrap()),
+            _ => {}
+        }
+    }
+}
+
+/// Apply `select=alias:column,...`, renaming aliased columns.
+fn project(row: Value, query: &str) -> Value {
+    let Some((_, columns)) = query_pairs(query).find(|(key, _)| *key == "select") else { return row };
+    let mut out = serde_json::Map::new();
+    for item in columns.split(',') {
+        let item = item.replace("%20", "");
+        let (alias, column) = item.split_once(':').unwrap_or((&item, &item));
+        out.insert(alias.trim().to_string(), row.get(column.trim()).cloned().unwrap_or(Value::Null));
+    }
+    Value::Object(out)
+}
+
+fn value_text(v: &Value) -> String {
+    match v {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Numeric comparison where both sides are integers, text otherwise.
+fn compare(field: &Value, value: &str) -> std::cmp::Ordering {
+    match (field.as_i64(), value.parse::<i64>()) {
+        (Some(lhs), Ok(rhs)) => lhs.cmp(&rhs),
+        _ => value_text(field).as_str().cmp(value),
+    }
+}
@@ -0,0 +1,100 @@
+use chronovox::{ChronoEvent, EventKind, PlaybackConfig, Region, SpawnState, Timeline};
+use tdt::core::TimeDelta;
+use uvoxid::UvoxId;
+use uvoxxyz::types::Cartesian;
+
+fn at(id: UvoxId, nanos: i64, kind: EventKind) -> ChronoEvent {
+    ChronoEvent::new(id, TimeDelta::from_ticks(nanos, "nanoseconds"), kind)
+}
+
+fn history() -> Timeline {
+    let pipe = UvoxId::earth(1, 0, 0);
+    let valve = UvoxId::earth(2, 0, 0);
+    let mut timeline = Timeline::new();
+    for e in [
+        at(pipe, 0, EventKind::Spawn),
+        at(valve, 5, EventKind::Spawn),
+        at(pipe, 10, EventKind::Move { offset: Cartesian { x: 1.0, y: 0.0, z: 0.0 } }),
+        at(pipe, 20, EventKind::TemperatureChange { delta_c: 5.0 }),
+        at(valve, 25, EventKind::Despawn),
+        at(
+            pipe,
+            30,
+            EventKind::Ambient {
+                region: Region::Frame(pipe.frame_id),
+                temperature_c: None,
+                pressure_pa: Some(90_000.0),
+                until: Some(TimeDelta::from_ticks(200, "nanoseconds")),
+            },
+        ),
+        at(pipe, 100, EventKind::TemperatureChange { delta_c: 1.0 }),
+        at(pipe, 300, EventKind::Move { offset: Cartesian { x: 0.0, y: 2.0, z: 0.0 } }),
+    ] {
+        timeline.insert(e).unwrap();
+    }
+    timeline
+}
+
+#[test]
+fn compaction_preserves_playback() {
+    let timeline = history();
+    let compacted = timeline.compact(50);
+
+    // pipe + valve snapshots (valve despawned), the live ambient field, and two later events
+    assert_eq!(compacted.len(), 6);
+    assert_eq!(compacted.playback(), timeline.playback());
+    assert_eq!(compacted.playback_until(150), timeline.playback_until(150));
+    assert_eq!(compacted.playback_until(50), timeline.playback_until(50));
+}
+
+#[test]
+fn compaction_preserves_relaxation_across_the_cutoff() {
+    let pipe = UvoxId::earth(1, 0, 0);
+    let hot = SpawnState { temperature: Some(80.0), thermal_tau_s: Some(1e-7), ..Default::default() };
+    let mut timeline = Timeline::new();
+    for e in [
+        ChronoEvent { payload: Some(hot.to_payload()), ..at(pipe, 0, EventKind::Spawn) },
+        at(
+            pipe,
+            30,
+            EventKind::Ambient {
+                region: Region::Frame(pipe.frame_id),
+                temperature_c: Some(5.0),
+                pressure_pa: None,
+                until: Some(TimeDelta::from_ticks(200, "nanoseconds")),
+            },
+        ),
+        at(pipe, 100, EventKind::TemperatureChange { delta_c: 10.0 }),
+        at(pipe, 300, EventKind::Move { offset: Cartesian { x: 0.0, y: 2.0, z: 0.0 } }),
+    ] {
+        timeline.insert(e).unwrap();
+    }
+
+    let config = PlaybackConfig::new().with_thermal_relaxation(true);
+    let compacted = timeline.compact_with(50, &config);
+    // The pipe is still cooling toward the field's 5 °C at the cutoff
+    let snapshot = compacted.playback_until_with(50, &config)[&pipe].temperature;
+    assert!(snapshot > 6.0 && snapshot < 79.0, "{snapshot}");
+
+    let (original, compacted) = (timeline.playback_with(&config), compacted.playback_with(&config));
+    let (a, b) = (&original[&pipe], &compacted[&pipe]);
+    assert!((a.temperature - b.temperature).abs() < 1e-9, "{} vs {}", a.temperature, b.temperature);
+    assert!((a.pos.y - b.pos.y).abs() < 1e-9);
+    assert_eq!((a.pressure, a.thermal_tau_s, a.alive), (b.pressure, b.thermal_tau_s, b.alive));
+}
+
+#[test]
+fn splits_partition_events() {
+    let timeline = history();
+
+    let by_entity = timeline.split_by_entity();
+    assert_eq!(by_entity.len(), 2);
+    assert_eq!(by_entity[&UvoxId::earth(2, 0, 0)].len(), 2);
+
+    let windows = timeline.split_windows(100);
+    assert_eq!(windows.keys().copied().collect::<Vec<_>>(), vec![0, 100, 300]);
+    assert_eq!(windows[&0].len(), 6);
+
+    let (before, after) = timeline.split_at(30);
+    assert_eq!((before.len(), after.len()), (6, 2));
+}
@@ -0,0 +1,78 @@
+use chronovox::{ChronoEvent, Condition, EventKind, PlaybackConfig, SpawnState, Region, Thresholds, Timeline};
+use uvoxid::UvoxId;
+use tdt::core::TimeDelta;
+
+fn at(id: UvoxId, nanos: i64, kind: EventKind) -> ChronoEvent {
+    ChronoEvent::new(id, TimeDelta::from_ticks(nanos, "nanoseconds"), kind)
+}
+
+fn copper() -> Thresholds {
+    Thresholds { freeze_c: Some(0.0), overheat_c: Some(90.0), max_pressure_pa: Some(500_000.0) }
+}
+
+#[test]
+fn thresholds_classify_with_pressure_first() {
+    let t = copper();
+    assert_eq!(t.evaluate(20.0, 101_325.0), Condition::Nominal);
+    assert_eq!(t.evaluate(-1.0, 101_325.0), Condition::Frozen);
+    assert_eq!(t.evaluate(95.0, 101_325.0), Condition::Overheated);
+    assert_eq!(t.evaluate(-1.0, 600_000.0), Condition::OverPressure);
+    assert_eq!(Thresholds::default().evaluate(-100.0, 1e9), Condition::Nominal);
+}
+
+#[test]
+fn playback_emits_transitions_for_material_thresholds() {
+    let pipe = UvoxId::earth(1, 0, 0);
+    let init = SpawnState { material: Some("copper".into()), ..Default::default() };
+
+    let mut timeline = Timeline::new();
+    timeline.insert(ChronoEvent { payload: Some(init.to_payload()), ..at(pipe, 0, EventKind::Spawn) }).unwrap();
+    timeline.insert(at(pipe, 100, EventKind::TemperatureChange { delta_c: -25.0 })).unwrap();
+    timeline.insert(at(pipe, 200, EventKind::TemperatureChange { delta_c: 30.0 })).unwrap();
+
+    let config = PlaybackConfig::new().with_material("copper", copper());
+    let (state, alerts) = timeline.playback_with_alerts(&config);
+
+    assert_eq!(state[&pipe].condition, Condition::Nominal);
+    let kinds: Vec<_> = alerts
+        .iter()
+        .map(|e| (e.t.ticks("nanoseconds"), format!("{:?}", e.kind)))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            (100, format!("{:?}", EventKind::ConditionChange { from: Condition::Nominal, to: Condition::Frozen })),
+            (200, format!("{:?}", EventKind::ConditionChange { from: Condition::Frozen, to: Condition::Nominal })),
+        ]
+    );
+
+    // Without a matching material there is nothing to trip
+    let (_, none) = timeline.playback_with_alerts(&PlaybackConfig::new());
+    assert!(none.is_empty());
+}
+
+#[test]
+fn simultaneous_transitions_are_ordered_by_entity() {
+    let init = SpawnState { material: Some("copper".into()), ..Default::default() };
+    let pipes: Vec<UvoxId> = [7, 3, 9, 1, 5, 8, 2, 6, 4].into_iter().map(|r| UvoxId::earth(r, 0, 0)).collect();
+
+    let mut timeline = Timeline::new();
+    for pipe in &pipes {
+        timeline.insert(ChronoEvent { payload: Some(init.to_payload()), ..at(*pipe, 0, EventKind::Spawn) }).unwrap();
+    }
+    // One cold snap freezes every pipe at once
+    timeline.insert(at(UvoxId::earth(0, 0, 0), 100, EventKind::Ambient {
+        region: Region::Frame(pipes[0].frame_id),
+        temperature_c: Some(-10.0),
+        pressure_pa: None,
+        until: None,
+    })).unwrap();
+
+    let config = PlaybackConfig::new().with_material("copper", copper());
+    let (_, alerts) = timeline.playback_with_alerts(&config);
+    let order: Vec<u64> = alerts.iter().map(|e| e.id.r_um).collect();
+    let mut sorted = order.clone();
+    sorted.sort_unstable();
+    assert_eq!(order.len(), pipes.len());
+    assert_eq!(order, sorted);
+}
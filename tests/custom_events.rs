@@ -0,0 +1,83 @@
+#[cfg(feature = "supabase")]
+mod common;
+
+use chronovox::{ChronoEvent, CustomEvent, EntityState, EventKind, PlaybackConfig, Timeline};
+#[cfg(feature = "supabase")]
+use chronovox::{fetch_events_for_entity, insert_event_for_entity};
+use serde::{Deserialize, Serialize};
+use tdt::core::TimeDelta;
+#[cfg(feature = "supabase")]
+use uuid::Uuid;
+use uvoxid::UvoxId;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Insulate {
+    thickness_mm: f64,
+}
+
+impl CustomEvent for Insulate {
+    const NAME: &'static str = "plumbing.insulate";
+
+    fn apply(&self, state: &mut EntityState) {
+        state.fields.insert("insulation_mm".into(), self.thickness_mm.into());
+    }
+}
+
+fn pipe() -> UvoxId {
+    UvoxId::earth(1, 0, 0)
+}
+
+fn insulate_event() -> ChronoEvent {
+    ChronoEvent::custom(pipe(), TimeDelta::from_ticks(10, "nanoseconds"), &Insulate { thickness_mm: 13.0 })
+        .unwrap()
+}
+
+#[test]
+fn typed_custom_event_roundtrips_through_serde() {
+    let e = insulate_event();
+    let json = serde_json::to_string(&e).unwrap();
+    let back: ChronoEvent = serde_json::from_str(&json).unwrap();
+
+    assert!(matches!(&back.kind, EventKind::Custom(name) if name == Insulate::NAME));
+    assert_eq!(back.as_custom::<Insulate>().unwrap().unwrap(), Insulate { thickness_mm: 13.0 });
+
+    let spawn = ChronoEvent { kind: EventKind::Spawn, ..e };
+    assert!(spawn.as_custom::<Insulate>().is_none());
+}
+
+#[test]
+fn registered_custom_event_is_applied_by_playback() {
+    let mut timeline = Timeline::new();
+    timeline.insert(ChronoEvent::new(pipe(), TimeDelta::from_ticks(0, "nanoseconds"), EventKind::Spawn)).unwrap();
+    timeline.insert(insulate_event()).unwrap();
+
+    let state = timeline.playback_with(&PlaybackConfig::new().with_custom::<Insulate>());
+    assert_eq!(state[&pipe()].fields.get("insulation_mm"), Some(&13.0.into()));
+
+    // Unregistered kinds are left alone
+    let state = timeline.playback();
+    assert!(!state[&pipe()].fields.contains_key("insulation_mm"));
+}
+
+#[cfg(feature = "supabase")]
+#[tokio::test]
+async fn kinds_roundtrip_through_persistence() {
+    let (_mock, supa) = common::MockSupabase::start().await;
+    let entity_id = Uuid::new_v4();
+
+    let moved = ChronoEvent::new(
+        pipe(),
+        TimeDelta::from_ticks(5, "nanoseconds"),
+        EventKind::Move { offset: chronovox::Cartesian { x: 1.0, y: 0.0, z: 0.0 } },
+    );
+    insert_event_for_entity(&supa, entity_id, &moved).await.unwrap();
+    insert_event_for_entity(&supa, entity_id, &insulate_event()).await.unwrap();
+
+    let timeline = fetch_events_for_entity(&supa, entity_id).await.unwrap();
+    assert_eq!(timeline.len(), 2);
+    assert!(matches!(timeline.events[0].kind, EventKind::Move { offset } if offset.x == 1.0));
+    assert_eq!(
+        timeline.events[1].as_custom::<Insulate>().unwrap().unwrap(),
+        Insulate { thickness_mm: 13.0 }
+    );
+}
@@ -0,0 +1,56 @@
+use chronovox::{ChronoEvent, EventKind, Timeline};
+use tdt::core::TimeDelta;
+use uvoxid::UvoxId;
+
+fn at(id: UvoxId, nanos: i64, kind: EventKind) -> ChronoEvent {
+    ChronoEvent::new(id, TimeDelta::from_ticks(nanos, "nanoseconds"), kind)
+}
+
+#[test]
+fn diff_reports_added_removed_modified_and_state() {
+    let pipe = UvoxId::earth(1, 0, 0);
+    let valve = UvoxId::earth(2, 0, 0);
+
+    let spawn = at(pipe, 0, EventKind::Spawn);
+    let heat = at(pipe, 10, EventKind::TemperatureChange { delta_c: 5.0 });
+    let shock = at(pipe, 20, EventKind::Shock { g: 3.0 });
+
+    let mut old = Timeline::new();
+    for e in [spawn.clone(), heat.clone(), shock.clone()] {
+        old.insert(e).unwrap();
+    }
+
+    // Re-imported: same spawn, heat re-read with a new value, shock gone,
+    // and a valve that wasn't there before
+    let mut new = Timeline::new();
+    new.insert(spawn).unwrap();
+    new.insert(ChronoEvent { kind: EventKind::TemperatureChange { delta_c: 7.0 }, ..heat.clone() }).unwrap();
+    new.insert(at(valve, 5, EventKind::Spawn)).unwrap();
+
+    let diff = old.diff(&new);
+    assert_eq!(diff.added.len(), 1);
+    assert_eq!(diff.added[0].id, valve);
+    assert_eq!(diff.removed.len(), 1);
+    assert_eq!(diff.removed[0].event_id, shock.event_id);
+    assert_eq!(diff.modified.len(), 1);
+    assert_eq!(diff.modified[0].0.event_id, heat.event_id);
+
+    assert_eq!(diff.state_changes.len(), 2);
+    let pipe_change = &diff.state_changes[0];
+    assert_eq!(pipe_change.id, pipe);
+    assert_eq!(pipe_change.before.as_ref().unwrap().temperature, 25.0);
+    assert_eq!(pipe_change.after.as_ref().unwrap().temperature, 27.0);
+    assert!(diff.state_changes[1].before.is_none());
+}
+
+#[test]
+fn events_with_fresh_ids_match_by_time_entity_and_kind() {
+    let pipe = UvoxId::earth(1, 0, 0);
+    let mut old = Timeline::new();
+    old.insert(at(pipe, 0, EventKind::Spawn)).unwrap();
+    let mut new = Timeline::new();
+    new.insert(at(pipe, 0, EventKind::Spawn)).unwrap(); // same event, new id
+
+    assert!(old.diff(&new).is_empty());
+    assert!(old.diff(&old).is_empty());
+}
@@ -0,0 +1,49 @@
+use chronovox::{Cartesian, ChronoEvent, Environment, EventKind, PlaybackConfig, Region, Timeline};
+use uvoxid::UvoxId;
+use tdt::core::TimeDelta;
+
+const EARTH_RADIUS_UM: u64 = 6_371_000_000_000;
+
+fn spawn(id: UvoxId) -> ChronoEvent {
+    ChronoEvent::new(id, TimeDelta::from_ticks(0, "nanoseconds"), EventKind::Spawn)
+}
+
+#[test]
+fn spawn_uses_frame_and_region_defaults() {
+    let surface = UvoxId::earth(EARTH_RADIUS_UM, 0, 0);
+    let underground = UvoxId::earth(EARTH_RADIUS_UM - 2_000_000, 0, 0); // 2 m down
+    let in_orbit = UvoxId::new(7, 1_000_000, 0, 0);
+
+    let soil = Environment::new(12.0, 101_325.0);
+    let config = PlaybackConfig::new()
+        .with_frame(7, Environment::VACUUM)
+        .with_region(
+            Region::Shell { frame_id: 0, min_r_um: 0, max_r_um: EARTH_RADIUS_UM - 1 },
+            soil,
+        );
+
+    let mut timeline = Timeline::new();
+    for id in [surface, underground, in_orbit] {
+        timeline.push(spawn(id));
+    }
+    let state = timeline.playback_with(&config);
+
+    assert_eq!(state[&surface].temperature, Environment::STANDARD.temperature);
+    assert_eq!(state[&underground].temperature, soil.temperature);
+    assert_eq!(state[&in_orbit].temperature, Environment::VACUUM.temperature);
+    assert_eq!(state[&in_orbit].pressure, 0.0);
+}
+
+#[test]
+fn region_box_contains_only_matching_frame() {
+    let region = Region::Box {
+        frame_id: 0,
+        min: Cartesian { x: 0.0, y: 0.0, z: 0.0 },
+        max: Cartesian { x: 10.0, y: 10.0, z: 3.0 },
+    };
+    let inside = Cartesian { x: 5.0, y: 5.0, z: 1.0 };
+
+    assert!(region.contains(0, &inside));
+    assert!(!region.contains(1, &inside));
+    assert!(!region.contains(0, &Cartesian { x: 5.0, y: 5.0, z: 4.0 }));
+}
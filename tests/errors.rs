@@ -0,0 +1,142 @@
+#![cfg(feature = "supabase")]
+
+mod common;
+
+use chronovox::{
+    fetch_events_for_entity, insert_event_for_entity, subscribe_to_entity, ChronoEvent, ChronovoxError, EventKind,
+};
+use futures::StreamExt;
+use serde_json::json;
+use std::pin::pin;
+use std::time::Duration;
+use tdt::core::TimeDelta;
+use uuid::Uuid;
+use uvoxid::UvoxId;
+
+fn spawn() -> ChronoEvent {
+    ChronoEvent::new(UvoxId::earth(1, 0, 0), TimeDelta::from_ticks(0, "nanoseconds"), EventKind::Spawn)
+}
+
+#[tokio::test]
+async fn unreachable_server_is_a_retryable_network_error() {
+    let err = fetch_events_for_entity(&common::offline().await, Uuid::new_v4()).await.unwrap_err();
+    assert!(matches!(err, ChronovoxError::Network(_)), "{err:?}");
+    assert!(err.is_retryable());
+}
+
+#[tokio::test]
+async fn postgrest_error_bodies_are_classified() {
+    let (mock, supa) = common::MockSupabase::start().await;
+    let entity_id = Uuid::new_v4();
+
+    mock.fail_next("429 Too Many Requests", json!({ "message": "API rate limit exceeded" }));
+    let err = fetch_events_for_entity(&supa, entity_id).await.unwrap_err();
+    assert!(matches!(err, ChronovoxError::RateLimited(_)), "{err:?}");
+    assert!(err.is_retryable());
+
+    mock.fail_next(
+        "404 Not Found",
+        json!({ "code": "42P01", "message": "relation \"public.events\" does not exist" }),
+    );
+    let err = fetch_events_for_entity(&supa, entity_id).await.unwrap_err();
+    assert!(matches!(err, ChronovoxError::SchemaMismatch(_)), "{err:?}");
+    assert!(!err.is_retryable());
+
+    mock.fail_next("401 Unauthorized", json!({ "message": "Invalid API key" }));
+    let err = insert_event_for_entity(&supa, entity_id, &spawn()).await.unwrap_err();
+    assert!(matches!(err, ChronovoxError::Unauthorized(_)), "{err:?}");
+    assert!(!err.is_retryable());
+    assert!(mock.rows().is_empty());
+}
+
+#[tokio::test]
+async fn responses_without_a_json_body_are_not_retried() {
+    let (mock, supa) = common::MockSupabase::start().await;
+    let entity_id = Uuid::new_v4();
+
+    // A proxy rejecting the request outright must not be retried forever
+    mock.fail_next_raw("413 Payload Too Large", "Payload Too Large");
+    let err = insert_event_for_entity(&supa, entity_id, &spawn()).await.unwrap_err();
+    assert!(matches!(err, ChronovoxError::Db(_)), "{err:?}");
+    assert!(!err.is_retryable());
+    assert!(mock.rows().is_empty());
+
+    // Without the status a gateway page looks the same
+    mock.fail_next_raw("502 Bad Gateway", "<html><body>502 Bad Gateway</body></html>");
+    let err = fetch_events_for_entity(&supa, entity_id).await.unwrap_err();
+    assert!(!err.is_retryable(), "{err:?}");
+
+    // Gateways that answer in JSON are still classified
+    mock.fail_next("502 Bad Gateway", json!({ "statusCode": 502, "error": "Bad Gateway" }));
+    let err = fetch_events_for_entity(&supa, entity_id).await.unwrap_err();
+    assert!(matches!(err, ChronovoxError::Network(_)), "{err:?}");
+
+    mock.fail_next("429 Too Many Requests", json!({ "statusCode": 429, "error": "Slow down" }));
+    let err = fetch_events_for_entity(&supa, entity_id).await.unwrap_err();
+    assert!(matches!(err, ChronovoxError::RateLimited(_)), "{err:?}");
+}
+
+#[tokio::test]
+async fn undecodable_rows_name_the_row() {
+    let (mock, supa) = common::MockSupabase::start().await;
+    let entity_id = Uuid::new_v4();
+    let row_id = Uuid::new_v4();
+    mock.rows.lock().unwrap().push(json!({
+        "id": row_id,
+        "entity_id": entity_id,
+        "ticks": 0,
+        "kind": "Spawn",
+    }));
+
+    let err = fetch_events_for_entity(&supa, entity_id).await.unwrap_err();
+    match err {
+        ChronovoxError::Decode { row_id: Some(id), .. } => assert_eq!(id, row_id),
+        other => panic!("expected a decode error, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn subscription_survives_transient_failures() {
+    let (mock, supa) = common::MockSupabase::start().await;
+    let entity_id = Uuid::new_v4();
+    let event = spawn();
+    insert_event_for_entity(&supa, entity_id, &event).await.unwrap();
+
+    mock.fail_next("503 Service Unavailable", json!({ "code": "PGRST001", "message": "database unavailable" }));
+    let mut events = pin!(subscribe_to_entity(&supa, entity_id, None, Duration::from_millis(10)));
+    let received = tokio::time::timeout(Duration::from_secs(2), events.next())
+        .await
+        .expect("retried after the failure")
+        .unwrap()
+        .unwrap();
+    assert_eq!(received.event_id, event.event_id);
+
+    mock.fail_next("404 Not Found", json!({ "code": "42P01", "message": "relation does not exist" }));
+    let err = events.next().await.unwrap().unwrap_err();
+    assert!(matches!(err, ChronovoxError::SchemaMismatch(_)), "{err:?}");
+}
+
+#[tokio::test]
+async fn kind_data_that_does_not_decode_is_an_error() {
+    let (mock, supa) = common::MockSupabase::start().await;
+    let entity_id = Uuid::new_v4();
+    let row_id = Uuid::new_v4();
+    mock.rows.lock().unwrap().push(json!({
+        "id": row_id,
+        "entity_id": entity_id,
+        "frame_id": 1,
+        "r_um": 0,
+        "lat_code": 0,
+        "lon_code": 0,
+        "version": 3,
+        "ticks": 0,
+        "kind": "TemperatureChange",
+        "kind_data": { "delta_f": 50.0 },
+    }));
+
+    let err = fetch_events_for_entity(&supa, entity_id).await.unwrap_err();
+    match err {
+        ChronovoxError::Decode { row_id: Some(id), .. } => assert_eq!(id, row_id),
+        other => panic!("expected a decode error, got {other:?}"),
+    }
+}
@@ -0,0 +1,66 @@
+#[cfg(feature = "supabase")]
+mod common;
+
+use chronovox::{ChronoEvent, EventKind, Timeline};
+#[cfg(feature = "supabase")]
+use chronovox::{fetch_events_for_entity, insert_event_for_entity};
+use tdt::core::TimeDelta;
+use uuid::Uuid;
+use uvoxid::UvoxId;
+
+fn at(nanos: i64, kind: EventKind) -> ChronoEvent {
+    ChronoEvent::new(UvoxId::earth(1, 0, 0), TimeDelta::from_ticks(nanos, "nanoseconds"), kind)
+}
+
+fn shock_fracture_leak() -> (ChronoEvent, ChronoEvent, ChronoEvent) {
+    let shock = at(10, EventKind::Shock { g: 12.0 });
+    let fracture = at(11, EventKind::Fracture { plane: "X-Y".into() }).caused_by(&shock);
+    let leak = at(12, EventKind::Leak { severity: 0.8 }).caused_by(&fracture);
+    (shock, fracture, leak)
+}
+
+#[test]
+fn causal_chain_is_traced_back_to_the_root() {
+    let (shock, fracture, leak) = shock_fracture_leak();
+    assert_eq!(leak.causation_id, Some(fracture.event_id));
+    assert_eq!(leak.correlation_id, Some(shock.event_id));
+
+    let mut timeline = Timeline::new();
+    timeline.insert(at(5, EventKind::Spawn)).unwrap();
+    for e in [leak.clone(), shock.clone(), fracture.clone()] {
+        timeline.insert(e).unwrap();
+    }
+
+    let chain: Vec<Uuid> = timeline.causal_chain(leak.event_id).iter().map(|e| e.event_id).collect();
+    assert_eq!(chain, vec![shock.event_id, fracture.event_id, leak.event_id]);
+    assert_eq!(timeline.query_by_correlation(shock.event_id).len(), 2);
+}
+
+#[test]
+fn ids_survive_serde() {
+    let (_, fracture, _) = shock_fracture_leak();
+    let back: ChronoEvent = serde_json::from_str(&serde_json::to_string(&fracture).unwrap()).unwrap();
+
+    assert_eq!(back.event_id, fracture.event_id);
+    assert_eq!(back.causation_id, fracture.causation_id);
+    assert_eq!(back.correlation_id, fracture.correlation_id);
+}
+
+#[cfg(feature = "supabase")]
+#[tokio::test]
+async fn ids_survive_persistence() {
+    let (_mock, supa) = common::MockSupabase::start().await;
+    let entity_id = Uuid::new_v4();
+    let (shock, fracture, leak) = shock_fracture_leak();
+
+    for e in [&shock, &fracture, &leak] {
+        let row_id = insert_event_for_entity(&supa, entity_id, e).await.unwrap();
+        assert_eq!(row_id, e.event_id);
+    }
+
+    let timeline = fetch_events_for_entity(&supa, entity_id).await.unwrap();
+    let fetched = timeline.find_event(leak.event_id).expect("leak fetched");
+    assert_eq!(fetched.causation_id, Some(fracture.event_id));
+    assert_eq!(fetched.correlation_id, Some(shock.event_id));
+    assert_eq!(timeline.causal_chain(leak.event_id).len(), 3);
+}
@@ -1,6 +1,5 @@
 use chronovox::{ChronoEvent, EventKind, UvoxId, TimeDelta, Cartesian};
 use uuid::Uuid;
-use serde_json;
 
 #[test]
 fn eventkind_serialization_roundtrip() {
@@ -13,36 +12,33 @@ fn eventkind_serialization_roundtrip() {
     let t = TimeDelta::from_now();
 
     let variants = vec![
-        ChronoEvent { id: base_id, t: t.clone(), kind: EventKind::Spawn, payload: None },
-        ChronoEvent { id: base_id, t: t.clone(), kind: EventKind::Despawn, payload: None },
-        ChronoEvent {
-            id: base_id,
-            t: t.clone(),
-            kind: EventKind::Move { offset: Cartesian { x: 1.0, y: 2.0, z: 3.0 } },
-            payload: None,
-        },
-        ChronoEvent {
-            id: base_id,
-            t: t.clone(),
-            kind: EventKind::Teleport { new_pos: Cartesian { x: -1.0, y: 0.5, z: 42.0 } },
-            payload: None,
-        },
-        ChronoEvent { id: base_id, t: t.clone(), kind: EventKind::TemperatureChange { delta_c: 100.0 }, payload: None },
-        ChronoEvent { id: base_id, t: t.clone(), kind: EventKind::PressureChange { delta_pa: 101325.0 }, payload: None },
-        ChronoEvent { id: base_id, t: t.clone(), kind: EventKind::Radiation { dose: 0.05 }, payload: None },
-        ChronoEvent { id: base_id, t: t.clone(), kind: EventKind::Shock { g: 9.81 }, payload: None },
-        ChronoEvent { id: base_id, t: t.clone(), kind: EventKind::Degrade { rate: 0.01 }, payload: None },
-        ChronoEvent { id: base_id, t: t.clone(), kind: EventKind::Leak { severity: 0.5 }, payload: None },
-        ChronoEvent { id: base_id, t: t.clone(), kind: EventKind::Fracture { plane: "X-Y".to_string() }, payload: None },
-        ChronoEvent { id: base_id, t: t.clone(), kind: EventKind::Bond { with: Uuid::new_v4() }, payload: None },
-        ChronoEvent { id: base_id, t: t.clone(), kind: EventKind::Unbond { from: Uuid::new_v4() }, payload: None },
-        ChronoEvent {
-            id: base_id,
-            t: t.clone(),
-            kind: EventKind::Transfer { to: Uuid::new_v4(), what: "water".into(), amount: 3.14 },
-            payload: None,
-        },
-        ChronoEvent { id: base_id, t, kind: EventKind::Custom("Magic".into()), payload: Some(serde_json::json!({"foo": "bar"})) },
+        ChronoEvent::new(base_id, t.clone(), EventKind::Spawn),
+        ChronoEvent::new(base_id, t.clone(), EventKind::Despawn),
+        ChronoEvent::new(
+            base_id,
+            t.clone(),
+            EventKind::Move { offset: Cartesian { x: 1.0, y: 2.0, z: 3.0 } },
+        ),
+        ChronoEvent::new(
+            base_id,
+            t.clone(),
+            EventKind::Teleport { new_pos: Cartesian { x: -1.0, y: 0.5, z: 42.0 } },
+        ),
+        ChronoEvent::new(base_id, t.clone(), EventKind::TemperatureChange { delta_c: 100.0 }),
+        ChronoEvent::new(base_id, t.clone(), EventKind::PressureChange { delta_pa: 101325.0 }),
+        ChronoEvent::new(base_id, t.clone(), EventKind::Radiation { dose: 0.05 }),
+        ChronoEvent::new(base_id, t.clone(), EventKind::Shock { g: 9.81 }),
+        ChronoEvent::new(base_id, t.clone(), EventKind::Degrade { rate: 0.01 }),
+        ChronoEvent::new(base_id, t.clone(), EventKind::Leak { severity: 0.5 }),
+        ChronoEvent::new(base_id, t.clone(), EventKind::Fracture { plane: "X-Y".to_string() }),
+        ChronoEvent::new(base_id, t.clone(), EventKind::Bond { with: Uuid::new_v4() }),
+        ChronoEvent::new(base_id, t.clone(), EventKind::Unbond { from: Uuid::new_v4() }),
+        ChronoEvent::new(
+            base_id,
+            t.clone(),
+            EventKind::Transfer { to: Uuid::new_v4(), what: "water".into(), amount: 2.5 },
+        ),
+        ChronoEvent::new(base_id, t, EventKind::Custom("Magic".into())).with_payload(serde_json::json!({"foo": "bar"})),
     ];
 
     for e in variants {
@@ -8,36 +8,28 @@ fn playback_handles_temp_and_pressure_changes() {
     let mut timeline = Timeline::new();
 
     // Spawn entity
-    timeline.push(ChronoEvent {
-        id,
-        t: TimeDelta::from_ticks(0, "nanoseconds"),
-        kind: EventKind::Spawn,
-        payload: None,
-    });
+    timeline.push(ChronoEvent::new(id, TimeDelta::from_ticks(0, "nanoseconds"), EventKind::Spawn));
 
     // Raise temperature by 10°C
-    timeline.push(ChronoEvent {
+    timeline.push(ChronoEvent::new(
         id,
-        t: TimeDelta::from_ticks(1, "nanoseconds"),
-        kind: EventKind::TemperatureChange { delta_c: 10.0 },
-        payload: None,
-    });
+        TimeDelta::from_ticks(1, "nanoseconds"),
+        EventKind::TemperatureChange { delta_c: 10.0 },
+    ));
 
     // Increase pressure by 500 Pascals
-    timeline.push(ChronoEvent {
+    timeline.push(ChronoEvent::new(
         id,
-        t: TimeDelta::from_ticks(2, "nanoseconds"),
-        kind: EventKind::PressureChange { delta_pa: 500.0 },
-        payload: None,
-    });
+        TimeDelta::from_ticks(2, "nanoseconds"),
+        EventKind::PressureChange { delta_pa: 500.0 },
+    ));
 
     // Teleport entity
-    timeline.push(ChronoEvent {
+    timeline.push(ChronoEvent::new(
         id,
-        t: TimeDelta::from_ticks(3, "nanoseconds"),
-        kind: EventKind::Teleport { new_pos: Cartesian { x: 5.0, y: -2.0, z: 1.0 } },
-        payload: None,
-    });
+        TimeDelta::from_ticks(3, "nanoseconds"),
+        EventKind::Teleport { new_pos: Cartesian { x: 5.0, y: -2.0, z: 1.0 } },
+    ));
 
     let state_map = timeline.playback();
     let state = state_map.get(&id).expect("entity state should exist");
@@ -0,0 +1,64 @@
+#![cfg(feature = "supabase")]
+
+mod common;
+
+use chronovox::{fetch_events_for_entity, insert_event_for_entity, insert_events, ChronoEvent, EventKind};
+use tdt::core::TimeDelta;
+use uuid::Uuid;
+use uvoxid::UvoxId;
+
+fn event(nanos: i64) -> ChronoEvent {
+    ChronoEvent::new(UvoxId::earth(1, 0, 0), TimeDelta::from_ticks(nanos, "nanoseconds"), EventKind::Shock { g: 1.0 })
+}
+
+#[tokio::test]
+async fn retried_inserts_do_not_duplicate() {
+    let (mock, supa) = common::MockSupabase::start().await;
+    let entity_id = Uuid::new_v4();
+    let first = event(10);
+
+    let id = insert_event_for_entity(&supa, entity_id, &first).await.unwrap();
+    assert_eq!(id, first.event_id);
+    // e.g. the first response timed out and the caller retries
+    assert_eq!(insert_event_for_entity(&supa, entity_id, &first).await.unwrap(), id);
+
+    // A batch overlapping what's stored inserts only the new rows
+    let batch = [first.clone(), event(20), event(30)];
+    let ids = insert_events(&supa, entity_id, &batch).await.unwrap();
+    assert_eq!(ids, batch.iter().map(|e| e.event_id).collect::<Vec<_>>());
+
+    assert_eq!(mock.rows().len(), 3);
+    assert_eq!(fetch_events_for_entity(&supa, entity_id).await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn retrying_a_partial_import_sends_only_missing_rows() {
+    let (mock, supa) = common::MockSupabase::start().await;
+    let entity_id = Uuid::new_v4();
+    let batch: Vec<ChronoEvent> = (0..200).map(event).collect();
+    insert_events(&supa, entity_id, &batch[..150]).await.unwrap();
+    let before = mock.requests.lock().unwrap().len();
+
+    let ids = insert_events(&supa, entity_id, &batch).await.unwrap();
+    assert_eq!(ids, batch.iter().map(|e| e.event_id).collect::<Vec<_>>());
+    assert_eq!(mock.rows().len(), 200);
+    // The rejected batch, two id lookups of 100, then the 50 missing rows
+    assert_eq!(mock.requests.lock().unwrap().len() - before, 4);
+}
+
+#[tokio::test]
+async fn unique_violations_on_other_columns_are_conflicts() {
+    let (mock, supa) = common::MockSupabase::start().await;
+    // e.g. a unique (entity_id, ticks) index on the team's table
+    for _ in 0..2 {
+        mock.fail_next_insert(
+            "409 Conflict",
+            serde_json::json!({ "code": "23505", "message": "duplicate key value violates unique constraint \"events_entity_ticks\"" }),
+        );
+    }
+
+    let err = insert_events(&supa, Uuid::new_v4(), &[event(10)]).await.unwrap_err();
+    assert!(matches!(err, chronovox::ChronovoxError::Conflict(_)), "{err:?}");
+    assert!(!err.is_retryable());
+    assert!(mock.rows().is_empty());
+}
@@ -0,0 +1,55 @@
+use chronovox::{Cartesian, ChronoEvent, EventKind, Timeline};
+use tdt::core::TimeDelta;
+use uvoxid::UvoxId;
+
+fn at(id: UvoxId, nanos: i64, kind: EventKind) -> ChronoEvent {
+    ChronoEvent::new(id, TimeDelta::from_ticks(nanos, "nanoseconds"), kind)
+}
+
+fn teleport(id: UvoxId, nanos: i64, x: f64) -> ChronoEvent {
+    at(id, nanos, EventKind::Teleport { new_pos: Cartesian { x, y: 0.0, z: 0.0 } })
+}
+
+fn timeline(events: Vec<ChronoEvent>) -> Timeline {
+    let mut t = Timeline::new();
+    for e in events {
+        t.insert(e).unwrap();
+    }
+    t
+}
+
+#[test]
+fn merge_interleaves_sorted_inputs_and_drops_duplicates() {
+    let pipe = UvoxId::earth(1, 0, 0);
+    let spawn = at(pipe, 0, EventKind::Spawn);
+
+    let sensor = timeline(vec![spawn.clone(), at(pipe, 20, EventKind::TemperatureChange { delta_c: 1.0 })]);
+    let user = timeline(vec![spawn, at(pipe, 10, EventKind::Shock { g: 2.0 }), at(pipe, 30, EventKind::Despawn)]);
+
+    let report = Timeline::merge([sensor, user]);
+    let ticks: Vec<i64> = report.timeline.iter_chronological().map(|e| e.t.ticks("nanoseconds")).collect();
+
+    assert_eq!(ticks, vec![0, 10, 20, 30]);
+    assert_eq!(report.duplicates, 1);
+    assert!(report.conflicts.is_empty());
+}
+
+#[test]
+fn merge_reports_contradicting_teleports() {
+    let pipe = UvoxId::earth(1, 0, 0);
+    let valve = UvoxId::earth(2, 0, 0);
+
+    let a = timeline(vec![teleport(pipe, 10, 1.0), teleport(valve, 10, 5.0)]);
+    let b = timeline(vec![teleport(pipe, 10, 2.0), teleport(valve, 10, 5.0)]);
+    let a_pipe = a.events[0].event_id;
+    let b_pipe = b.events[0].event_id;
+
+    let report = Timeline::merge([a, b]);
+
+    assert_eq!(report.timeline.len(), 4); // all kept
+    assert_eq!(report.conflicts.len(), 1); // the valve reports agree
+    let conflict = &report.conflicts[0];
+    assert_eq!(conflict.id, pipe);
+    assert_eq!(conflict.t_ns, 10);
+    assert_eq!(conflict.events, vec![(0, a_pipe), (1, b_pipe)]);
+}
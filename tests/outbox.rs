@@ -0,0 +1,101 @@
+#![cfg(feature = "supabase")]
+
+mod common;
+
+use chronovox::{insert_events, Backoff, ChronoEvent, EventKind, Outbox};
+use std::time::Duration;
+use tdt::core::TimeDelta;
+use uuid::Uuid;
+use uvoxid::UvoxId;
+
+fn spawn() -> ChronoEvent {
+    ChronoEvent::new(UvoxId::earth(1, 0, 0), TimeDelta::from_ticks(0, "nanoseconds"), EventKind::Spawn)
+}
+
+#[test]
+fn backoff_doubles_up_to_max() {
+    let backoff = Backoff { base: Duration::from_secs(1), max: Duration::from_secs(5) };
+    let delays: Vec<u64> = (1..=4).map(|n| backoff.delay(n).as_secs()).collect();
+    assert_eq!(delays, vec![1, 2, 4, 5]);
+}
+
+#[tokio::test]
+async fn queued_events_survive_restarts_and_sync_later() {
+    let path = std::env::temp_dir().join(format!("chronovox-outbox-{}.json", Uuid::new_v4()));
+    let entity_id = Uuid::new_v4();
+    let event = spawn();
+    let backoff = Backoff { base: Duration::from_millis(300), max: Duration::from_millis(300) };
+
+    let mut outbox = Outbox::open(&path).unwrap().with_backoff(backoff);
+    outbox.enqueue(entity_id, event.clone()).unwrap();
+    let status = outbox.sync(&common::offline().await).await.unwrap();
+    assert_eq!(status.pending, 1);
+    assert!(status.last_error.is_some());
+    assert_eq!(outbox.pending()[0].attempts, 1);
+
+    // Back online, but the retry isn't due yet
+    let (mock, supa) = common::MockSupabase::start().await;
+    outbox.sync(&supa).await.unwrap();
+    assert!(mock.rows().is_empty());
+    drop(outbox);
+
+    // After a restart, once due
+    tokio::time::sleep(Duration::from_millis(350)).await;
+    let mut outbox = Outbox::open(&path).unwrap();
+    let status = outbox.sync(&supa).await.unwrap();
+    assert_eq!((status.pending, status.synced), (0, 1));
+    assert_eq!(mock.rows()[0]["id"], event.event_id.to_string());
+    assert!(Outbox::open(&path).unwrap().pending().is_empty());
+
+    std::fs::remove_file(&path).ok();
+}
+
+#[tokio::test]
+async fn permanent_failures_are_parked_until_unparked() {
+    let path = std::env::temp_dir().join(format!("chronovox-outbox-{}.json", Uuid::new_v4()));
+    let (mock, supa) = common::MockSupabase::start().await;
+    let backoff = Backoff { base: Duration::ZERO, max: Duration::ZERO };
+
+    let mut outbox = Outbox::open(&path).unwrap().with_backoff(backoff);
+    outbox.enqueue(Uuid::new_v4(), spawn()).unwrap();
+
+    // A gateway error is transient: retried, not parked
+    mock.fail_next("503 Service Unavailable", serde_json::json!({ "statusCode": 503, "error": "upstream unavailable" }));
+    let status = outbox.sync(&supa).await.unwrap();
+    assert_eq!((status.pending, status.parked), (1, 0));
+
+    mock.fail_next("401 Unauthorized", serde_json::json!({ "message": "Invalid API key" }));
+    let status = outbox.sync(&supa).await.unwrap();
+    assert_eq!((status.pending, status.parked), (0, 1));
+
+    // Parked entries aren't retried, even across restarts
+    let mut outbox = Outbox::open(&path).unwrap().with_backoff(backoff);
+    outbox.sync(&supa).await.unwrap();
+    assert!(mock.rows().is_empty());
+
+    outbox.unpark().unwrap();
+    let status = outbox.sync(&supa).await.unwrap();
+    assert_eq!((status.pending, status.parked, status.synced), (0, 0, 1));
+    assert_eq!(mock.rows().len(), 1);
+
+    std::fs::remove_file(&path).ok();
+}
+
+#[tokio::test]
+async fn replaying_an_entry_already_stored_syncs_it() {
+    let path = std::env::temp_dir().join(format!("chronovox-outbox-{}.json", Uuid::new_v4()));
+    let (mock, supa) = common::MockSupabase::start().await;
+    let entity_id = Uuid::new_v4();
+    let event = spawn();
+
+    let mut outbox = Outbox::open(&path).unwrap();
+    outbox.enqueue(entity_id, event.clone()).unwrap();
+    // An earlier attempt was written, but its response never arrived
+    insert_events(&supa, entity_id, [&event]).await.unwrap();
+
+    let status = outbox.sync(&supa).await.unwrap();
+    assert_eq!((status.pending, status.parked, status.synced), (0, 0, 1));
+    assert_eq!(mock.rows().len(), 1);
+
+    std::fs::remove_file(&path).ok();
+}
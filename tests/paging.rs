@@ -0,0 +1,79 @@
+#![cfg(feature = "supabase")]
+
+mod common;
+
+use chronovox::{
+    fetch_events_for_entity, fetch_events_in_range, fetch_events_page, insert_events, stream_events_for_entity,
+    ChronoEvent, EventKind, EventQuery,
+};
+use futures::TryStreamExt;
+use tdt::core::TimeDelta;
+use uuid::Uuid;
+use uvoxid::UvoxId;
+
+/// Events inserted out of order, with two sharing each tick.
+async fn seed(supa: &supabasic::Supabase, entity_id: Uuid) {
+    let id = UvoxId::earth(1, 0, 0);
+    let events: Vec<ChronoEvent> = [50, 10, 30, 20, 40, 10, 30, 50, 20, 40]
+        .into_iter()
+        .map(|t| ChronoEvent::new(id, TimeDelta::from_ticks(t, "nanoseconds"), EventKind::Shock { g: 1.0 }))
+        .collect();
+    insert_events(supa, entity_id, &events).await.unwrap();
+}
+
+fn ticks(events: &[ChronoEvent]) -> Vec<i64> {
+    events.iter().map(|e| e.t.ticks("nanoseconds")).collect()
+}
+
+#[tokio::test]
+async fn pages_walk_a_range_in_order() {
+    let (_mock, supa) = common::MockSupabase::start().await;
+    let entity_id = Uuid::new_v4();
+    seed(&supa, entity_id).await;
+
+    let full = fetch_events_for_entity(&supa, entity_id).await.unwrap();
+    assert_eq!(ticks(&full.events), vec![10, 10, 20, 20, 30, 30, 40, 40, 50, 50]);
+
+    // Page boundaries fall between events sharing a tick
+    let mut query = EventQuery::new().with_range(20, 40).with_limit(3);
+    let first = fetch_events_page(&supa, entity_id, &query).await.unwrap();
+    assert_eq!(ticks(&first.timeline.events), vec![20, 20, 30]);
+
+    query = query.with_after(first.next.unwrap());
+    let second = fetch_events_page(&supa, entity_id, &query).await.unwrap();
+    assert_eq!(ticks(&second.timeline.events), vec![30, 40, 40]);
+    assert_eq!(
+        full.events[5].event_id,
+        second.timeline.events[0].event_id,
+        "resumes after the cursor, not at the next tick"
+    );
+
+    let third = fetch_events_page(&supa, entity_id, &query.with_after(second.next.unwrap())).await.unwrap();
+    assert!(third.timeline.is_empty());
+    assert!(third.next.is_none());
+}
+
+#[tokio::test]
+async fn fetch_in_range_collects_every_page() {
+    let (_mock, supa) = common::MockSupabase::start().await;
+    let entity_id = Uuid::new_v4();
+    seed(&supa, entity_id).await;
+
+    let slice = fetch_events_in_range(&supa, entity_id, 15, 45).await.unwrap();
+    assert_eq!(ticks(&slice.events), vec![20, 20, 30, 30, 40, 40]);
+}
+
+#[tokio::test]
+async fn paging_continues_past_pages_the_server_cut_short() {
+    let (mock, supa) = common::MockSupabase::start().await;
+    let entity_id = Uuid::new_v4();
+    seed(&supa, entity_id).await;
+    mock.set_max_rows(4);
+
+    let slice = fetch_events_in_range(&supa, entity_id, 15, 45).await.unwrap();
+    assert_eq!(ticks(&slice.events), vec![20, 20, 30, 30, 40, 40]);
+
+    let streamed: Vec<ChronoEvent> =
+        stream_events_for_entity(&supa, entity_id, EventQuery::new()).try_collect().await.unwrap();
+    assert_eq!(ticks(&streamed), vec![10, 10, 20, 20, 30, 30, 40, 40, 50, 50]);
+}
@@ -1,16 +1,5 @@
 // tests/persist.rs
-use supabasic::Supabase;
-use supabasic::entities::Entity;
-use uuid::Uuid;
-use dotenvy::dotenv;
-use std::env;
-use chrono::Utc;
-
-use chronovox::{insert_event_for_entity, fetch_events_for_entity, ChronoEvent, EventKind, Timeline};
-use uvoxid::UvoxId;
-use tdt::core::TimeDelta;
-use serde_json::json;
-
+#![cfg(feature = "supabase")]
 
 #[tokio::test]
 async fn test_insert_and_fetch_event() {
@@ -36,5 +25,5 @@ async fn test_insert_and_fetch_event() {
         .unwrap();
     println!("Fetched timeline with {} events", timeline.len());
 
-    assert!(timeline.len() > 0, "Timeline should contain at least one event");
+    assert!(!timeline.is_empty(), "Timeline should contain at least one event");
 }
@@ -1,18 +1,14 @@
-use chronovox::{ChronoEvent, EventKind, Timeline, EntityState};
+use chronovox::{ChronoEvent, EventKind, Timeline};
 use uvoxid::UvoxId;
-use uvoxxyz::types::Cartesian;
+use uvoxxyz::convert::UvoxIdExt;
+use uvoxxyz::types::{Cartesian, CoordSystem};
 use tdt::core::TimeDelta;
 use chrono::{Utc, Duration};
 
 fn make_event(anchor: &UvoxId, nanos: i64, kind: EventKind) -> ChronoEvent {
     let start = Utc::now();
     let end = start + Duration::nanoseconds(nanos);
-    ChronoEvent {
-        id: anchor.clone(),
-        t: TimeDelta::between(start, end),
-        kind,
-        payload: None,
-    }
+    ChronoEvent::new(*anchor, TimeDelta::between(start, end), kind)
 }
 
 #[test]
@@ -20,15 +16,17 @@ fn playback_applies_spawn_move_despawn() {
     let mut timeline = Timeline::new();
     let anchor = UvoxId::earth(6_371_000_000, 0, 0);
 
-    timeline.insert(make_event(&anchor, 1000, EventKind::Spawn));
-    timeline.insert(make_event(&anchor, 2000, EventKind::Move { offset: Cartesian { x: 1.0, y: 2.0, z: 0.0 } }));
-    timeline.insert(make_event(&anchor, 3000, EventKind::Despawn));
+    timeline.insert(make_event(&anchor, 1000, EventKind::Spawn)).unwrap();
+    timeline.insert(make_event(&anchor, 2000, EventKind::Move { offset: Cartesian { x: 1.0, y: 2.0, z: 0.0 } })).unwrap();
+    timeline.insert(make_event(&anchor, 3000, EventKind::Despawn)).unwrap();
 
     let state = timeline.playback();
 
+    // Spawned at the anchor's location, then moved by the offset
+    let origin = anchor.to_cartesian(CoordSystem::Math);
     let entity = state.get(&anchor).expect("entity missing");
-    assert_eq!(entity.pos.x, 1.0);
-    assert_eq!(entity.pos.y, 2.0);
-    assert_eq!(entity.pos.z, 0.0);
-    assert_eq!(entity.alive, false);
+    assert_eq!(entity.pos.x, origin.x + 1.0);
+    assert_eq!(entity.pos.y, origin.y + 2.0);
+    assert_eq!(entity.pos.z, origin.z);
+    assert!(!entity.alive);
 }
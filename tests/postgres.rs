@@ -0,0 +1,118 @@
+// Runs against the Postgres in `DATABASE_URL`, e.g.
+// DATABASE_URL=postgres://postgres@localhost/chronovox cargo test --features postgres
+#![cfg(feature = "postgres")]
+
+use chronovox::{ChronoEvent, ChronovoxError, EventKind, EventQuery, EventTable, PayloadSchemas, PgEventStore};
+use futures::StreamExt;
+use std::pin::pin;
+use std::time::Duration;
+use tdt::core::TimeDelta;
+use uuid::Uuid;
+use uvoxid::UvoxId;
+
+async fn store() -> Option<PgEventStore> {
+    dotenvy::dotenv().ok();
+    let Ok(url) = std::env::var("DATABASE_URL") else {
+        // CI runs these in a job with a Postgres service; never pass silently there
+        assert!(std::env::var_os("CI").is_none(), "DATABASE_URL must be set in CI");
+        eprintln!("DATABASE_URL not set; skipping");
+        return None;
+    };
+    // A table per run, with a renamed column to exercise the mapping
+    let table = EventTable::new(format!("events_{}", Uuid::new_v4().simple())).with_column("ticks", "t_ns").unwrap();
+    let store = PgEventStore::connect_with(&url, table).await.unwrap();
+    store.migrate().await.unwrap();
+    Some(store)
+}
+
+fn event(nanos: i64, kind: EventKind) -> ChronoEvent {
+    ChronoEvent::new(UvoxId::earth(1, 0, 0), TimeDelta::from_ticks(nanos, "nanoseconds"), kind)
+}
+
+#[tokio::test]
+async fn batch_insert_is_transactional_and_idempotent() {
+    let Some(mut store) = store().await else { return };
+    let entity_id = Uuid::new_v4();
+    let events = [event(20, EventKind::Shock { g: 2.0 }), event(10, EventKind::Spawn)];
+
+    let ids = store.insert_events(entity_id, &events).await.unwrap();
+    assert_eq!(ids, vec![events[0].event_id, events[1].event_id]);
+    // Retrying the same batch plus one new event stores only the new one
+    let mut retry = events.to_vec();
+    retry.push(event(30, EventKind::Despawn));
+    store.insert_events(entity_id, &retry).await.unwrap();
+
+    let timeline = store.fetch_events_for_entity(entity_id, &PayloadSchemas::new()).await.unwrap();
+    let ticks: Vec<i64> = timeline.iter_chronological().map(|e| e.t.ticks("nanoseconds")).collect();
+    assert_eq!(ticks, vec![10, 20, 30]);
+
+    let page = store
+        .fetch_events_page(entity_id, &EventQuery::new().with_range(15, 35).with_limit(1), &PayloadSchemas::new())
+        .await
+        .unwrap();
+    assert_eq!(page.timeline.events[0].event_id, events[0].event_id);
+    assert!(page.next.is_some());
+}
+
+#[tokio::test]
+async fn recorded_at_is_the_insert_time() {
+    let Some(mut store) = store().await else { return };
+    let entity_id = Uuid::new_v4();
+    let backdated = event(0, EventKind::Spawn).with_recorded_at(chrono::Utc::now() - chrono::Duration::days(2));
+    store.insert_events(entity_id, [&backdated]).await.unwrap();
+
+    let timeline = store.fetch_events_for_entity(entity_id, &PayloadSchemas::new()).await.unwrap();
+    assert!(timeline.events[0].recorded_at > backdated.recorded_at + chrono::Duration::days(1));
+}
+
+#[tokio::test]
+async fn subscription_wakes_on_notify() {
+    let Some(mut store) = store().await else { return };
+    let entity_id = Uuid::new_v4();
+    store.insert_events(entity_id, &[event(0, EventKind::Spawn)]).await.unwrap();
+
+    let mut events = pin!(store.subscribe(entity_id, None).await.unwrap());
+    assert!(events.next().await.unwrap().is_ok());
+
+    let later = event(10, EventKind::TemperatureChange { delta_c: 1.0 });
+    store.insert_events(entity_id, [&later]).await.unwrap();
+    let received = tokio::time::timeout(Duration::from_secs(5), events.next()).await.unwrap().unwrap().unwrap();
+    assert_eq!(received.event_id, later.event_id);
+}
+
+#[tokio::test]
+async fn database_errors_map_to_the_shared_variants() {
+    if store().await.is_none() {
+        return;
+    }
+    let url = std::env::var("DATABASE_URL").unwrap();
+    // Never migrated
+    let missing = PgEventStore::connect_with(&url, EventTable::new(format!("missing_{}", Uuid::new_v4().simple())))
+        .await
+        .unwrap();
+
+    let err = missing.fetch_events_for_entity(Uuid::new_v4(), &PayloadSchemas::new()).await.unwrap_err();
+    assert!(matches!(err, ChronovoxError::SchemaMismatch(_)), "{err:?}");
+    assert!(!err.is_retryable());
+}
+
+#[tokio::test]
+async fn inserts_leave_extra_columns_to_their_defaults() {
+    let Some(mut store) = store().await else { return };
+    let url = std::env::var("DATABASE_URL").unwrap();
+    let (client, connection) = tokio_postgres::connect(&url, tokio_postgres::NoTls).await.unwrap();
+    tokio::spawn(connection);
+    let table = store.table().name.clone();
+    client
+        .batch_execute(&format!(r#"alter table "{table}" add column created_at timestamptz not null default now()"#))
+        .await
+        .unwrap();
+
+    let entity_id = Uuid::new_v4();
+    store.insert_events(entity_id, &[event(0, EventKind::Spawn)]).await.unwrap();
+    let row = client
+        .query_one(&format!(r#"select count(*) from "{table}" where created_at is not null"#), &[])
+        .await
+        .unwrap();
+    assert_eq!(row.get::<_, i64>(0), 1);
+}
@@ -0,0 +1,52 @@
+#[cfg(feature = "supabase")]
+mod common;
+
+use chrono::{DateTime, Duration, Utc};
+use chronovox::{ChronoEvent, EventKind, Timeline};
+#[cfg(feature = "supabase")]
+use chronovox::{fetch_events_for_entity, insert_event_for_entity};
+use tdt::core::TimeDelta;
+#[cfg(feature = "supabase")]
+use uuid::Uuid;
+use uvoxid::UvoxId;
+
+fn reading(nanos: i64, recorded_at: DateTime<Utc>) -> ChronoEvent {
+    ChronoEvent::new(
+        UvoxId::earth(1, 0, 0),
+        TimeDelta::from_ticks(nanos, "nanoseconds"),
+        EventKind::TemperatureChange { delta_c: -1.0 },
+    )
+    .with_recorded_at(recorded_at)
+    .with_source("sensor-7")
+    .with_author("ingest")
+}
+
+#[test]
+fn known_at_excludes_events_recorded_later() {
+    let decision = Utc::now();
+    let mut timeline = Timeline::new();
+    timeline.insert(reading(100, decision - Duration::minutes(5))).unwrap();
+    // Occurred earlier, but the sensor reported it after the decision
+    timeline.insert(reading(50, decision + Duration::minutes(5))).unwrap();
+
+    assert_eq!(timeline.known_at(decision).len(), 1);
+    assert_eq!(timeline.query_known_at(decision, 0, 75).len(), 0);
+    assert_eq!(timeline.query_known_at(decision + Duration::hours(1), 0, 75).len(), 1);
+    assert_eq!(timeline.query_by_source("sensor-7").len(), 2);
+}
+
+#[cfg(feature = "supabase")]
+#[tokio::test]
+async fn provenance_survives_persistence() {
+    let (_mock, supa) = common::MockSupabase::start().await;
+    let entity_id = Uuid::new_v4();
+    let before = Utc::now();
+    // A client clock two days behind doesn't backdate what the store knows
+    insert_event_for_entity(&supa, entity_id, &reading(10, before - Duration::days(2))).await.unwrap();
+
+    let timeline = fetch_events_for_entity(&supa, entity_id).await.unwrap();
+    let e = &timeline.events[0];
+    assert!(e.recorded_at >= before, "{}", e.recorded_at);
+    assert_eq!(e.source.as_deref(), Some("sensor-7"));
+    assert_eq!(e.author.as_deref(), Some("ingest"));
+}
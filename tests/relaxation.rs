@@ -0,0 +1,61 @@
+use chronovox::{Cartesian, ChronoEvent, Environment, EventKind, PlaybackConfig, Region, SpawnState, Timeline};
+use uvoxid::UvoxId;
+use tdt::core::TimeDelta;
+
+const SECOND_NS: i64 = 1_000_000_000;
+
+fn spawn_pipe(id: UvoxId, temperature: f64, tau_s: f64) -> ChronoEvent {
+    let init = SpawnState {
+        pos: Some(Cartesian { x: 1.0, y: 1.0, z: 1.0 }),
+        temperature: Some(temperature),
+        thermal_tau_s: Some(tau_s),
+        ..Default::default()
+    };
+    ChronoEvent::new(id, TimeDelta::from_ticks(0, "nanoseconds"), EventKind::Spawn).with_payload(init.to_payload())
+}
+
+#[test]
+fn temperature_relaxes_toward_ambient_at_cutoff() {
+    let pipe = UvoxId::earth(1, 0, 0);
+    let mut timeline = Timeline::new();
+    timeline.insert(spawn_pipe(pipe, 20.0, 10.0)).unwrap();
+
+    let winter = PlaybackConfig::new()
+        .with_default_env(Environment::new(-10.0, 101_325.0))
+        .with_thermal_relaxation(true);
+
+    // After one time constant: 63% of the way to ambient
+    let state = timeline.playback_until_with(10 * SECOND_NS, &winter);
+    let expected = -10.0 + 30.0 * (-1.0f64).exp();
+    assert!((state[&pipe].temperature - expected).abs() < 1e-9);
+
+    // Disabled by default
+    let off = timeline.playback_until_with(10 * SECOND_NS, &winter.clone().with_thermal_relaxation(false));
+    assert_eq!(off[&pipe].temperature, 20.0);
+}
+
+#[test]
+fn relaxation_follows_ambient_fields_until_they_end() {
+    let pipe = UvoxId::earth(1, 0, 0);
+    let cold_snap = UvoxId::earth(0, 0, 0);
+    let mut timeline = Timeline::new();
+    timeline.insert(spawn_pipe(pipe, 20.0, 1.0)).unwrap();
+    timeline.insert(ChronoEvent::new(
+        cold_snap,
+        TimeDelta::from_ticks(0, "nanoseconds"),
+        EventKind::Ambient {
+            region: Region::Frame(0),
+            temperature_c: Some(-20.0),
+            pressure_pa: None,
+            until: Some(TimeDelta::from_ticks(SECOND_NS, "nanoseconds")),
+        },
+    )).unwrap();
+
+    let config = PlaybackConfig::new().with_thermal_relaxation(true);
+    let state = timeline.playback_until_with(2 * SECOND_NS, &config);
+
+    // One second toward -20 °C, then one second back toward the 20 °C default
+    let after_snap = -20.0 + 40.0 * (-1.0f64).exp();
+    let expected = 20.0 + (after_snap - 20.0) * (-1.0f64).exp();
+    assert!((state[&pipe].temperature - expected).abs() < 1e-9);
+}
@@ -0,0 +1,90 @@
+#[cfg(feature = "supabase")]
+mod common;
+
+use chronovox::{ChronoEvent, ChronovoxError, EventKind, PayloadSchemas, SpawnState, Timeline};
+#[cfg(feature = "supabase")]
+use chronovox::{fetch_events_for_entity_with, insert_event_for_entity};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tdt::core::TimeDelta;
+#[cfg(feature = "supabase")]
+use uuid::Uuid;
+use uvoxid::UvoxId;
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct LeakReport {
+    litres_per_min: f64,
+}
+
+fn schemas() -> PayloadSchemas {
+    PayloadSchemas::new()
+        .register::<SpawnState>("Spawn")
+        .require::<LeakReport>("Leak")
+        .register_fn("Leak", |payload: &Value| match payload["litres_per_min"].as_f64() {
+            Some(rate) if rate < 0.0 => vec!["litres_per_min must not be negative".into()],
+            _ => Vec::new(),
+        })
+}
+
+fn leak(payload: Option<Value>) -> ChronoEvent {
+    ChronoEvent {
+        payload,
+        ..ChronoEvent::new(
+            UvoxId::earth(1, 0, 0),
+            TimeDelta::from_ticks(10, "nanoseconds"),
+            EventKind::Leak { severity: 0.5 },
+        )
+    }
+}
+
+#[test]
+fn validated_reports_every_violation() {
+    let schemas = schemas();
+
+    assert!(leak(Some(json!({ "litres_per_min": 2.0 }))).validated(&schemas).is_ok());
+
+    match leak(None).validated(&schemas) {
+        Err(ChronovoxError::InvalidPayload { kind, violations }) => {
+            assert_eq!(kind, "Leak");
+            assert_eq!(violations, vec!["payload is required"]);
+        }
+        other => panic!("expected InvalidPayload, got {other:?}"),
+    }
+
+    match leak(Some(json!({ "litres_per_min": -1.0 }))).validated(&schemas) {
+        Err(ChronovoxError::InvalidPayload { violations, .. }) => assert_eq!(violations.len(), 1),
+        other => panic!("expected InvalidPayload, got {other:?}"),
+    }
+
+    // Unregistered kinds accept anything
+    let shock = ChronoEvent { kind: EventKind::Shock { g: 3.0 }, ..leak(Some(json!("anything"))) };
+    assert!(shock.validated(&schemas).is_ok());
+}
+
+#[test]
+fn insert_rejects_invalid_payloads() {
+    let mut timeline = Timeline::with_schemas(schemas());
+    let bad_spawn = ChronoEvent {
+        kind: EventKind::Spawn,
+        ..leak(Some(json!({ "temperature": "hot" })))
+    };
+
+    assert!(timeline.insert(bad_spawn).is_err());
+    assert!(timeline.insert(leak(Some(json!({ "litres_per_min": 1.0 })))).is_ok());
+    assert_eq!(timeline.len(), 1);
+}
+
+#[cfg(feature = "supabase")]
+#[tokio::test]
+async fn fetch_rejects_invalid_rows() {
+    let (_mock, supa) = common::MockSupabase::start().await;
+    let entity_id = Uuid::new_v4();
+    insert_event_for_entity(&supa, entity_id, &leak(Some(json!({ "rate": 1 })))).await.unwrap();
+
+    let err = fetch_events_for_entity_with(&supa, entity_id, &schemas()).await.unwrap_err();
+    assert!(matches!(err, ChronovoxError::InvalidPayload { .. }), "{err}");
+
+    let unchecked = fetch_events_for_entity_with(&supa, entity_id, &PayloadSchemas::new()).await.unwrap();
+    assert_eq!(unchecked.len(), 1);
+}
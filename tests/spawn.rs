@@ -0,0 +1,53 @@
+use chronovox::{ChronoEvent, EventKind, SpawnState, Timeline};
+use uvoxid::UvoxId;
+use uvoxxyz::convert::UvoxIdExt;
+use uvoxxyz::types::{Cartesian, CoordSystem};
+use tdt::core::TimeDelta;
+use serde_json::json;
+
+#[test]
+fn spawn_starts_at_event_location() {
+    let anchor = UvoxId::earth(6_371_000_000, 45_000_000, 90_000_000);
+    let mut timeline = Timeline::new();
+    timeline.push(ChronoEvent::new(
+        anchor,
+        TimeDelta::from_ticks(0, "nanoseconds"),
+        EventKind::Spawn,
+    ));
+
+    let state = timeline.playback();
+    let e = state.get(&anchor).expect("entity state should exist");
+    let expected = anchor.to_cartesian(CoordSystem::Math);
+
+    assert_eq!(e.pos, expected);
+    assert!(e.pos.z > 0.0, "northern hemisphere should have z > 0");
+    assert_eq!(e.temperature, 20.0);
+    assert_eq!(e.pressure, 101_325.0);
+}
+
+#[test]
+fn spawn_payload_overrides_initial_state() {
+    let anchor = UvoxId::earth(6_371_000_000, 0, 0);
+    let init = SpawnState {
+        pos: Some(Cartesian { x: 1.0, y: 2.0, z: 3.0 }),
+        temperature: Some(-5.0),
+        pressure: None,
+        fields: [("material".to_string(), json!("copper"))].into_iter().collect(),
+        ..Default::default()
+    };
+
+    let mut timeline = Timeline::new();
+    timeline.push(ChronoEvent::new(
+        anchor,
+        TimeDelta::from_ticks(0, "nanoseconds"),
+        EventKind::Spawn,
+    ).with_payload(init.to_payload()));
+
+    let state = timeline.playback();
+    let e = state.get(&anchor).unwrap();
+
+    assert_eq!(e.pos, Cartesian { x: 1.0, y: 2.0, z: 3.0 });
+    assert_eq!(e.temperature, -5.0);
+    assert_eq!(e.pressure, 101_325.0); // not overridden
+    assert_eq!(e.fields.get("material"), Some(&json!("copper")));
+}
@@ -0,0 +1,112 @@
+#![cfg(feature = "supabase")]
+
+mod common;
+
+use chronovox::{
+    insert_events, stream_events_for_entity, ChronoEvent, ChronovoxError, EventKind, EventQuery, Playback,
+    PlaybackConfig, Timeline,
+};
+use futures::{StreamExt, TryStreamExt};
+use std::pin::pin;
+use tdt::core::TimeDelta;
+use uuid::Uuid;
+use uvoxid::UvoxId;
+
+fn history() -> Timeline {
+    let id = UvoxId::earth(1, 0, 0);
+    let mut timeline = Timeline::new();
+    timeline.insert(ChronoEvent::new(id, TimeDelta::from_ticks(0, "nanoseconds"), EventKind::Spawn)).unwrap();
+    for t in 1..=9 {
+        timeline
+            .insert(ChronoEvent::new(
+                id,
+                TimeDelta::from_ticks(t, "nanoseconds"),
+                EventKind::TemperatureChange { delta_c: 1.0 },
+            ))
+            .unwrap();
+    }
+    timeline
+}
+
+#[tokio::test]
+async fn stream_fetches_pages_lazily() {
+    let (mock, supa) = common::MockSupabase::start().await;
+    let entity_id = Uuid::new_v4();
+    insert_events(&supa, entity_id, &history()).await.unwrap();
+    let before = mock.requests.lock().unwrap().len();
+
+    let mut events = pin!(stream_events_for_entity(&supa, entity_id, EventQuery::new().with_limit(4)));
+    let first: Vec<ChronoEvent> = events.as_mut().take(3).try_collect().await.unwrap();
+    assert_eq!(first.len(), 3);
+    assert_eq!(mock.requests.lock().unwrap().len() - before, 1);
+
+    let rest: Vec<ChronoEvent> = events.try_collect().await.unwrap();
+    assert_eq!(rest.len(), 7);
+    // Pages of 4, 4 and 2, then the empty page ending the stream
+    assert_eq!(mock.requests.lock().unwrap().len() - before, 4);
+}
+
+#[tokio::test]
+async fn follow_builds_state_incrementally() {
+    let (_mock, supa) = common::MockSupabase::start().await;
+    let entity_id = Uuid::new_v4();
+    let timeline = history();
+    insert_events(&supa, entity_id, &timeline).await.unwrap();
+
+    let mut playback = Playback::new(PlaybackConfig::new());
+    let events = stream_events_for_entity(&supa, entity_id, EventQuery::new().with_limit(4));
+    let temperatures: Vec<f64> = playback
+        .follow(events)
+        .map_ok(|(_, state)| state.temperature)
+        .try_collect()
+        .await
+        .unwrap();
+
+    assert_eq!(temperatures.first(), Some(&20.0));
+    assert_eq!(temperatures.last(), Some(&29.0));
+    assert_eq!(playback.state(), &timeline.playback());
+}
+
+#[tokio::test]
+async fn follow_honours_retractions_and_corrections() {
+    let (_mock, supa) = common::MockSupabase::start().await;
+    let entity_id = Uuid::new_v4();
+    let id = UvoxId::earth(1, 0, 0);
+    let at = |nanos: i64, kind| ChronoEvent::new(id, TimeDelta::from_ticks(nanos, "nanoseconds"), kind);
+    let ns = |nanos| TimeDelta::from_ticks(nanos, "nanoseconds");
+
+    let bogus = at(10, EventKind::TemperatureChange { delta_c: 80.0 });
+    let misread = at(20, EventKind::TemperatureChange { delta_c: 50.0 });
+    let correction = ChronoEvent::correction_of(&misread, ns(40), at(20, EventKind::TemperatureChange { delta_c: 5.0 }));
+    let mut timeline = Timeline::new();
+    for e in [
+        at(0, EventKind::Spawn),
+        bogus.clone(),
+        misread,
+        ChronoEvent::retraction_of(&bogus, ns(30)),
+        correction,
+    ] {
+        timeline.insert(e).unwrap();
+    }
+    insert_events(&supa, entity_id, &timeline).await.unwrap();
+
+    // Without the history, amendments can't be applied
+    let mut playback = Playback::new(PlaybackConfig::new());
+    let events = stream_events_for_entity(&supa, entity_id, EventQuery::new());
+    let updates: Vec<_> = playback.follow(events).collect().await;
+    assert_eq!(updates.len(), 4);
+    let err = updates.last().unwrap().as_ref().unwrap_err();
+    assert!(matches!(err, ChronovoxError::UnresolvedAmendment(_)), "{err:?}");
+
+    let mut playback = Playback::new(PlaybackConfig::new());
+    let events = stream_events_for_entity(&supa, entity_id, EventQuery::new());
+    let temperatures: Vec<f64> = playback
+        .follow_with_history(events)
+        .map_ok(|(_, state)| state.temperature)
+        .try_collect()
+        .await
+        .unwrap();
+
+    assert_eq!(temperatures, vec![20.0, 100.0, 150.0, 70.0, 25.0]);
+    assert_eq!(playback.state(), &timeline.playback());
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "supabase")]
+
+mod common;
+
+use chronovox::{
+    insert_event_for_entity, insert_events, subscribe_to_entity, ChronoEvent, EventKind, Playback,
+    PlaybackConfig,
+};
+use futures::StreamExt;
+use std::pin::pin;
+use std::time::Duration;
+use tdt::core::TimeDelta;
+use uuid::Uuid;
+use uvoxid::UvoxId;
+
+fn event(nanos: i64, kind: EventKind) -> ChronoEvent {
+    ChronoEvent::new(UvoxId::earth(1, 0, 0), TimeDelta::from_ticks(nanos, "nanoseconds"), kind)
+}
+
+#[tokio::test]
+async fn subscription_replays_then_follows_new_events() {
+    let (_mock, supa) = common::MockSupabase::start().await;
+    let entity_id = Uuid::new_v4();
+    insert_events(
+        &supa,
+        entity_id,
+        &[event(0, EventKind::Spawn), event(10, EventKind::TemperatureChange { delta_c: 5.0 })],
+    )
+    .await
+    .unwrap();
+
+    let mut playback = Playback::new(PlaybackConfig::new());
+    let events = subscribe_to_entity(&supa, entity_id, None, Duration::from_millis(10));
+    let mut updates = pin!(playback.follow(events));
+
+    for expected in [20.0, 25.0] {
+        let (_, state) = updates.next().await.unwrap().unwrap();
+        assert_eq!(state.temperature, expected);
+    }
+
+    // Nothing new yet: the subscription keeps polling
+    let idle = tokio::time::timeout(Duration::from_millis(50), updates.next()).await;
+    assert!(idle.is_err());
+
+    insert_event_for_entity(&supa, entity_id, &event(20, EventKind::TemperatureChange { delta_c: -2.0 }))
+        .await
+        .unwrap();
+    let (_, state) = tokio::time::timeout(Duration::from_secs(2), updates.next())
+        .await
+        .expect("new event delivered")
+        .unwrap()
+        .unwrap();
+    assert_eq!(state.temperature, 23.0);
+}
@@ -0,0 +1,66 @@
+#[cfg(feature = "supabase")]
+mod common;
+
+use chronovox::{ChronovoxError, EventTable};
+#[cfg(feature = "supabase")]
+use chronovox::{ChronoEvent, EventKind, EventQuery, PayloadSchemas};
+#[cfg(feature = "supabase")]
+use tdt::core::TimeDelta;
+#[cfg(feature = "supabase")]
+use uuid::Uuid;
+#[cfg(feature = "supabase")]
+use uvoxid::UvoxId;
+
+fn legacy_table() -> EventTable {
+    EventTable::new("plumbing_events")
+        .with_column("entity_id", "asset_id")
+        .and_then(|table| table.with_column("ticks", "t_ns"))
+        .and_then(|table| table.with_column("id", "event_uuid"))
+        .unwrap()
+}
+
+#[test]
+fn unknown_columns_are_rejected() {
+    let err = EventTable::new("events").with_column("tick", "t_ns").unwrap_err();
+    assert!(matches!(err, ChronovoxError::SchemaMismatch(_)), "{err:?}");
+}
+
+#[test]
+fn shipped_migration_matches_default_layout() {
+    let shipped = include_str!("../migrations/0001_create_events.sql");
+    assert_eq!(shipped, EventTable::default().migration_sql());
+
+    let sql = legacy_table().migration_sql();
+    assert!(sql.contains("create table if not exists \"plumbing_events\""));
+    assert!(sql.contains("(\"asset_id\", \"t_ns\", \"event_uuid\")"));
+}
+
+#[cfg(feature = "supabase")]
+#[tokio::test]
+async fn mapped_table_round_trips() {
+    let (mock, supa) = common::MockSupabase::start().await;
+    let table = legacy_table();
+    let entity_id = Uuid::new_v4();
+    let id = UvoxId::earth(1, 0, 0);
+    let events = [
+        ChronoEvent::new(id, TimeDelta::from_ticks(20, "nanoseconds"), EventKind::Shock { g: 2.0 }),
+        ChronoEvent::new(id, TimeDelta::from_ticks(10, "nanoseconds"), EventKind::Spawn),
+    ];
+
+    let ids = table.insert_events(&supa, entity_id, &events).await.unwrap();
+    assert_eq!(ids[0], events[0].event_id);
+    let row = &mock.rows()[0];
+    assert_eq!(row["asset_id"], entity_id.to_string());
+    assert_eq!(row["t_ns"], 20);
+    assert!(row.get("ticks").is_none());
+
+    let timeline = table.fetch_events_for_entity(&supa, entity_id, &PayloadSchemas::new()).await.unwrap();
+    let order: Vec<Uuid> = timeline.iter_chronological().map(|e| e.event_id).collect();
+    assert_eq!(order, vec![events[1].event_id, events[0].event_id]);
+
+    let page = table
+        .fetch_events_page(&supa, entity_id, &EventQuery::new().with_range(15, 25), &PayloadSchemas::new())
+        .await
+        .unwrap();
+    assert_eq!(page.timeline.len(), 1);
+}
@@ -14,12 +14,11 @@ fn can_create_and_store_event() {
     let end = start + Duration::nanoseconds(1234);
     let td = TimeDelta::between(start, end);
 
-    let e = ChronoEvent {
-        id: anchor,
-        t: td,
-        kind: EventKind::Move { offset: Cartesian { x: 1.0, y: 0.0, z: 0.0 } },
-        payload: None,
-    };
+    let e = ChronoEvent::new(
+        anchor,
+        td,
+        EventKind::Move { offset: Cartesian { x: 1.0, y: 0.0, z: 0.0 } },
+    );
 
     timeline.push(e.clone());
     assert_eq!(timeline.events.len(), 1);
@@ -29,5 +28,5 @@ fn can_create_and_store_event() {
 
     // Verify nanos via ticks()
     let nanos = timeline.events[0].t.ticks("nanoseconds");
-    assert!(nanos >= 1234 && nanos < 2000, "nanos = {}", nanos);
+    assert!((1234..2000).contains(&nanos), "nanos = {}", nanos);
 }
@@ -7,12 +7,7 @@ use chrono::{Utc, Duration};
 fn make_event(anchor: &UvoxId, nanos: i64, kind: EventKind) -> ChronoEvent {
     let start = Utc::now();
     let end = start + Duration::nanoseconds(nanos);
-    ChronoEvent {
-        id: anchor.clone(),
-        t: TimeDelta::between(start, end),
-        kind,
-        payload: None,
-    }
+    ChronoEvent::new(*anchor, TimeDelta::between(start, end), kind)
 }
 
 #[test]
@@ -25,9 +20,9 @@ fn timeline_orders_events_correctly() {
     let e2 = make_event(&anchor, 1000, EventKind::Custom("early".into()));
     let e3 = make_event(&anchor, 9000, EventKind::Custom("late".into()));
 
-    timeline.insert(e1);
-    timeline.insert(e2);
-    timeline.insert(e3);
+    timeline.insert(e1).unwrap();
+    timeline.insert(e2).unwrap();
+    timeline.insert(e3).unwrap();
 
     let labels: Vec<String> = timeline
         .iter_chronological()
@@ -49,9 +44,9 @@ fn query_time_range_filters_properly() {
     let e2 = make_event(&anchor, 2000, EventKind::Move { offset: Cartesian { x: 1.0, y: 0.0, z: 0.0 } });
     let e3 = make_event(&anchor, 5000, EventKind::Despawn);
 
-    timeline.insert(e1);
-    timeline.insert(e2);
-    timeline.insert(e3);
+    timeline.insert(e1).unwrap();
+    timeline.insert(e2).unwrap();
+    timeline.insert(e3).unwrap();
 
     // Only the first two should fall within this range
     let filtered = timeline.query_time_range(0, 3000);
@@ -67,8 +62,8 @@ fn query_by_id_filters_properly() {
     let e1 = make_event(&house, 1000, EventKind::Spawn);
     let e2 = make_event(&tree, 2000, EventKind::Spawn);
 
-    timeline.insert(e1);
-    timeline.insert(e2);
+    timeline.insert(e1).unwrap();
+    timeline.insert(e2).unwrap();
 
     let house_events = timeline.query_by_id(&house);
     assert_eq!(house_events.len(), 1);
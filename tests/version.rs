@@ -0,0 +1,143 @@
+#[cfg(feature = "supabase")]
+mod common;
+
+use chronovox::{upcast, Cartesian, ChronoEvent, ChronovoxError, EventKind, EVENT_VERSION};
+#[cfg(feature = "supabase")]
+use chronovox::fetch_events_for_entity;
+use serde_json::json;
+use tdt::core::TimeDelta;
+use uvoxid::UvoxId;
+
+fn legacy_json() -> serde_json::Value {
+    // Written before events carried a version tag
+    json!({
+        "id": UvoxId::earth(1, 0, 0),
+        "t": TimeDelta::from_ticks(42, "nanoseconds"),
+        "kind": { "TemperatureChange": { "delta_c": 4.5 } },
+    })
+}
+
+#[test]
+fn unversioned_json_is_upcast_to_current() {
+    let event: ChronoEvent = serde_json::from_value(legacy_json()).unwrap();
+    assert_eq!(event.version, EVENT_VERSION);
+    assert_eq!(event.t.ticks("nanoseconds"), 42);
+    assert!(matches!(event.kind, EventKind::TemperatureChange { delta_c } if delta_c == 4.5));
+    assert!(event.payload.is_none());
+
+    let upcast = upcast(legacy_json()).unwrap();
+    assert_eq!(upcast["version"], json!(EVENT_VERSION));
+}
+
+#[test]
+fn pre_id_events_get_a_stable_id() {
+    let first: ChronoEvent = serde_json::from_value(legacy_json()).unwrap();
+    let again: ChronoEvent = serde_json::from_str(&legacy_json().to_string()).unwrap();
+    assert_eq!(first.event_id, again.event_id);
+
+    let mut other = legacy_json();
+    other["t"] = json!(TimeDelta::from_ticks(43, "nanoseconds"));
+    let other: ChronoEvent = serde_json::from_value(other).unwrap();
+    assert_ne!(first.event_id, other.event_id);
+}
+
+#[test]
+fn serialized_events_carry_their_version() {
+    let event = ChronoEvent::new(UvoxId::earth(1, 0, 0), TimeDelta::from_ticks(1, "nanoseconds"), EventKind::Spawn);
+    let value = serde_json::to_value(&event).unwrap();
+    assert_eq!(value["version"], json!(EVENT_VERSION));
+}
+
+#[test]
+fn future_versions_are_rejected() {
+    let mut future = legacy_json();
+    future["version"] = json!(EVENT_VERSION + 1);
+
+    assert!(matches!(upcast(future.clone()), Err(ChronovoxError::UnsupportedVersion(v)) if v == EVENT_VERSION + 1));
+    let err = serde_json::from_value::<ChronoEvent>(future).unwrap_err();
+    assert!(err.to_string().contains("Unsupported event version"), "{err}");
+}
+
+#[test]
+fn legacy_debug_kinds_are_parsed() {
+    let bond = uuid::Uuid::new_v4();
+    for kind in [
+        EventKind::Spawn,
+        EventKind::TemperatureChange { delta_c: -2.5 },
+        EventKind::Move { offset: Cartesian { x: 1.0, y: -0.5, z: 1e-7 } },
+        EventKind::Fracture { plane: "x \"north\"\\south".into() },
+        EventKind::Bond { with: bond },
+        EventKind::Transfer { to: bond, what: "water".into(), amount: 3.0 },
+        EventKind::Custom("Magic".into()),
+    ] {
+        let mut value = legacy_json();
+        value["kind"] = json!(format!("{kind:?}"));
+        let event: ChronoEvent = serde_json::from_value(value).unwrap();
+        assert_eq!(format!("{:?}", event.kind), format!("{kind:?}"));
+    }
+
+    let mut garbled = legacy_json();
+    garbled["kind"] = json!("TemperatureChange { delta_c: ");
+    assert!(matches!(upcast(garbled), Err(ChronovoxError::Decode { .. })));
+}
+
+#[cfg(feature = "supabase")]
+#[tokio::test]
+async fn baseline_rows_are_read_back() {
+    let (mock, supa) = common::MockSupabase::start().await;
+    let entity_id = uuid::Uuid::new_v4();
+    let id = UvoxId::earth(1, 0, 0);
+    // Exactly what the first release's insert_event_for_entity wrote
+    for (ticks, kind) in [(0, EventKind::Spawn), (10, EventKind::TemperatureChange { delta_c: 10.0 })] {
+        mock.rows.lock().unwrap().push(json!({
+            "id": uuid::Uuid::new_v4(),
+            "entity_id": entity_id,
+            "frame_id": id.frame_id as i64,
+            "r_um": id.r_um as i64,
+            "lat_code": id.lat_code,
+            "lon_code": id.lon_code,
+            "ticks": ticks,
+            "timestamp": chrono::Utc::now(),
+            "kind": format!("{kind:?}"),
+            "move_offset": null,
+            "payload": null,
+        }));
+    }
+
+    let timeline = fetch_events_for_entity(&supa, entity_id).await.unwrap();
+    assert!(matches!(timeline.events[1].kind, EventKind::TemperatureChange { delta_c } if delta_c == 10.0));
+    assert_eq!(timeline.playback()[&id].temperature, 30.0);
+}
+
+#[cfg(feature = "supabase")]
+#[tokio::test]
+async fn versioned_kind_data_rows_are_read_back() {
+    let (mock, supa) = common::MockSupabase::start().await;
+    let entity_id = uuid::Uuid::new_v4();
+    let id = UvoxId::earth(1, 0, 0);
+    // A v1 row: kind_data columns, but no recorded_at
+    for (ticks, kind, data) in [
+        (0, "Spawn", json!(null)),
+        (10, "TemperatureChange", json!({ "delta_c": 10.0 })),
+        (20, "Move", json!({ "offset": { "x": 1.0, "y": 0.0, "z": 0.0 } })),
+    ] {
+        mock.rows.lock().unwrap().push(json!({
+            "id": uuid::Uuid::new_v4(),
+            "entity_id": entity_id,
+            "frame_id": id.frame_id as i64,
+            "r_um": id.r_um as i64,
+            "lat_code": id.lat_code,
+            "lon_code": id.lon_code,
+            "version": 1,
+            "ticks": ticks,
+            "kind": kind,
+            "kind_data": data,
+        }));
+    }
+
+    let timeline = fetch_events_for_entity(&supa, entity_id).await.unwrap();
+    assert!(timeline.events.iter().all(|event| event.version == EVENT_VERSION));
+    assert!(matches!(timeline.events[1].kind, EventKind::TemperatureChange { delta_c } if delta_c == 10.0));
+    assert!(matches!(timeline.events[2].kind, EventKind::Move { offset } if offset.x == 1.0));
+    assert_eq!(timeline.playback()[&id].temperature, 30.0);
+}